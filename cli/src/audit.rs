@@ -0,0 +1,66 @@
+//! Cross-source disagreement detection.
+//!
+//! Unlike `commands::import_bulk`'s same-source conflict detection (two
+//! values from *one* source_id), this flags genuine scientific disagreement:
+//! a controlled-vocabulary field where two or more distinct values are each
+//! backed by a *different* source, coexisting silently as separate data
+//! points with no signal to the user.
+
+use std::collections::HashMap;
+
+use crate::models::OakEntry;
+
+/// One field where sources disagree: each distinct value mapped to every
+/// source_id asserting it.
+#[derive(Debug, Clone)]
+pub struct Disagreement {
+    pub field_name: String,
+    pub values: Vec<(String, Vec<String>)>,
+}
+
+/// Finds every field in `enumerated_fields` (expected to come from
+/// `SchemaValidator::get_enumerated_fields`, since only controlled-vocabulary
+/// fields have well-defined equality between sources) where `entry` holds two
+/// or more distinct values, each backed by a different source.
+pub fn detect_disagreements(entry: &OakEntry, enumerated_fields: &[&str]) -> Vec<Disagreement> {
+    let mut disagreements = Vec::new();
+
+    for &field_name in enumerated_fields {
+        let mut by_value: HashMap<&str, Vec<String>> = HashMap::new();
+        for dp in entry.get_field(field_name) {
+            by_value
+                .entry(dp.value.as_str())
+                .or_default()
+                .push(dp.source_id.clone());
+        }
+
+        let distinct_sources: std::collections::HashSet<&String> =
+            by_value.values().flatten().collect();
+
+        if by_value.len() > 1 && distinct_sources.len() > 1 {
+            let mut values: Vec<(String, Vec<String>)> = by_value
+                .into_iter()
+                .map(|(value, sources)| (value.to_string(), sources))
+                .collect();
+            values.sort_by(|a, b| a.0.cmp(&b.0));
+
+            disagreements.push(Disagreement {
+                field_name: field_name.to_string(),
+                values,
+            });
+        }
+    }
+
+    disagreements
+}
+
+/// Renders a `Disagreement` as a single report line, e.g.
+/// `leaf_color: 'blue-green' (src2) vs 'green' (src1)`.
+pub fn format_disagreement(d: &Disagreement) -> String {
+    let parts: Vec<String> = d
+        .values
+        .iter()
+        .map(|(value, sources)| format!("'{}' ({})", value, sources.join(", ")))
+        .collect();
+    format!("{}: {}", d.field_name, parts.join(" vs "))
+}
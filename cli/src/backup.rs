@@ -0,0 +1,175 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chacha20poly1305::aead::{Aead, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, KeyInit, Nonce};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::db::Database;
+use crate::models::{AttributeDef, OakEntry, Source};
+
+/// Every source, oak entry, and registered attribute, serialized together
+/// as one exportable unit, independent of the SQLite schema.
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupPayload {
+    sources: Vec<Source>,
+    oak_entries: Vec<OakEntry>,
+    // Defaults to empty so a backup written before this field existed still imports.
+    #[serde(default)]
+    attributes: Vec<AttributeDef>,
+}
+
+// On-disk layout: MAGIC || salt || nonce || ciphertext-with-tag.
+const MAGIC: &[u8; 8] = b"OAKBAK01";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+fn derive_key(passphrase: &SecretString, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.expose_secret().as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+/// Writes `db`'s sources and oak entries to `out_path` as a single
+/// authenticated, passphrase-encrypted blob.
+pub fn export(db: &Database, out_path: &Path, passphrase: &SecretString) -> Result<()> {
+    let payload = BackupPayload {
+        sources: db.list_sources()?,
+        oak_entries: db.list_oak_entries()?,
+        attributes: db.list_attributes()?,
+    };
+    let plaintext = serde_json::to_vec(&payload).context("Failed to serialize backup payload")?;
+
+    let salt: [u8; SALT_LEN] = rand::random();
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = ChaCha20Poly1305::new((&key).into());
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_ref())
+        .map_err(|e| anyhow!("Failed to encrypt backup: {}", e))?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + SALT_LEN + nonce.len() + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+
+    std::fs::write(out_path, out)
+        .with_context(|| format!("Failed to write backup to {}", out_path.display()))
+}
+
+/// Decrypts a blob written by `export` and saves every source and oak
+/// entry it contains into `db`, inserting new sources and updating ones
+/// that already exist.
+pub fn import(db: &Database, in_path: &Path, passphrase: &SecretString) -> Result<()> {
+    let raw = std::fs::read(in_path)
+        .with_context(|| format!("Failed to read backup from {}", in_path.display()))?;
+
+    let header_len = MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if raw.len() < header_len {
+        bail!("Backup file is too short to be valid");
+    }
+    if &raw[..MAGIC.len()] != MAGIC {
+        bail!("Not an oak compendium backup file (bad magic)");
+    }
+
+    let salt = &raw[MAGIC.len()..MAGIC.len() + SALT_LEN];
+    let nonce = Nonce::from_slice(&raw[MAGIC.len() + SALT_LEN..header_len]);
+    let ciphertext = &raw[header_len..];
+
+    let key = derive_key(passphrase, salt)?;
+    let cipher = ChaCha20Poly1305::new((&key).into());
+    let plaintext = cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| anyhow!("Failed to decrypt backup: wrong passphrase, or the file is corrupted or tampered with"))?;
+
+    let payload: BackupPayload =
+        serde_json::from_slice(&plaintext).context("Backup contents were not valid")?;
+
+    // Attributes must land before oak entries: save_oak_entry rejects any
+    // attribute name the target database doesn't recognize.
+    for attribute in &payload.attributes {
+        db.register_attribute(attribute)?;
+    }
+    for source in &payload.sources {
+        if db.get_source(&source.source_id)?.is_some() {
+            db.update_source(source)?;
+        } else {
+            db.insert_source(source)?;
+        }
+    }
+    for entry in &payload.oak_entries {
+        db.save_oak_entry(entry)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AttributeDef, DataPoint, OakEntry};
+
+    #[test]
+    fn test_export_then_import_round_trips_sources_entries_and_attributes() {
+        let source_db = Database::open(":memory:", None).unwrap();
+        source_db
+            .insert_source(&Source::new(
+                "src1".to_string(),
+                "book".to_string(),
+                "Some Book".to_string(),
+            ))
+            .unwrap();
+        source_db
+            .register_attribute(&AttributeDef {
+                name: "bark_texture".to_string(),
+                display_label: "Bark Texture".to_string(),
+                value_type: "text".to_string(),
+                cardinality: "single".to_string(),
+            })
+            .unwrap();
+        let mut entry = OakEntry::new("Quercus alba".to_string());
+        entry.set_field(
+            "bark_texture",
+            vec![DataPoint {
+                value: "scaly".to_string(),
+                source_id: "src1".to_string(),
+                page_number: None,
+            }],
+        );
+        source_db.save_oak_entry(&entry).unwrap();
+
+        let backup_file = tempfile::NamedTempFile::new().unwrap();
+        let passphrase = SecretString::from("correct horse battery staple".to_string());
+        export(&source_db, backup_file.path(), &passphrase).unwrap();
+
+        let target_db = Database::open(":memory:", None).unwrap();
+        import(&target_db, backup_file.path(), &passphrase).unwrap();
+
+        let restored = target_db.get_oak_entry("Quercus alba").unwrap().unwrap();
+        assert_eq!(restored.get_field("bark_texture")[0].value, "scaly");
+        assert!(target_db.get_source("src1").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_import_rejects_wrong_passphrase() {
+        let source_db = Database::open(":memory:", None).unwrap();
+        let backup_file = tempfile::NamedTempFile::new().unwrap();
+        export(
+            &source_db,
+            backup_file.path(),
+            &SecretString::from("right passphrase".to_string()),
+        )
+        .unwrap();
+
+        let target_db = Database::open(":memory:", None).unwrap();
+        let result = import(
+            &target_db,
+            backup_file.path(),
+            &SecretString::from("wrong passphrase".to_string()),
+        );
+        assert!(result.is_err());
+    }
+}
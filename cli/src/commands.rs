@@ -0,0 +1,17 @@
+pub mod add_value;
+pub mod attribute;
+pub mod audit;
+pub mod backup;
+pub mod db;
+pub mod delete;
+pub mod edit;
+pub mod find;
+pub mod import_bulk;
+pub mod lint;
+pub mod new;
+pub mod query;
+pub mod reformat;
+pub mod revert;
+#[cfg(feature = "server")]
+pub mod serve;
+pub mod source;
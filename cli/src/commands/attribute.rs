@@ -0,0 +1,42 @@
+use anyhow::Result;
+
+use crate::db::Database;
+use crate::models::AttributeDef;
+
+/// Execute the 'oak attribute add' command: register a new attribute (or
+/// update an existing one's label/type/cardinality), making it a legal
+/// `OakEntry::attributes` key without touching the database schema.
+pub fn add(db: &Database, name: &str, display_label: &str, value_type: &str, cardinality: &str) -> Result<()> {
+    db.register_attribute(&AttributeDef {
+        name: name.to_string(),
+        display_label: display_label.to_string(),
+        value_type: value_type.to_string(),
+        cardinality: cardinality.to_string(),
+    })?;
+
+    eprintln!("✓ Registered attribute '{}'", name);
+
+    Ok(())
+}
+
+/// Execute the 'oak attribute list' command
+pub fn list(db: &Database) -> Result<()> {
+    let attributes = db.list_attributes()?;
+
+    if attributes.is_empty() {
+        println!("No attributes registered.");
+        return Ok(());
+    }
+
+    println!("{:<20} {:<20} {:<10} {}", "Name", "Display Label", "Type", "Cardinality");
+    println!("{}", "=".repeat(80));
+
+    for attribute in attributes {
+        println!(
+            "{:<20} {:<20} {:<10} {}",
+            attribute.name, attribute.display_label, attribute.value_type, attribute.cardinality
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+
+use crate::audit;
+use crate::db::Database;
+use crate::schema::SchemaValidator;
+
+/// Execute the 'oak audit' command: scan every entry for cross-source
+/// disagreements on controlled-vocabulary fields and report them.
+pub fn execute(db: &Database, validator: &SchemaValidator) -> Result<()> {
+    let enumerated_fields = validator.get_enumerated_fields();
+    let entries = db.list_oak_entries()?;
+
+    let mut flagged = 0usize;
+
+    eprintln!("=== Oak Audit Report ===\n");
+
+    for entry in &entries {
+        let disagreements = audit::detect_disagreements(entry, &enumerated_fields);
+        if disagreements.is_empty() {
+            continue;
+        }
+
+        eprintln!("{}:", entry.scientific_name);
+        for d in &disagreements {
+            eprintln!("  {}", audit::format_disagreement(d));
+        }
+        eprintln!();
+        flagged += 1;
+    }
+
+    eprintln!(
+        "=== Summary: {} entries with cross-source disagreement(s), out of {} ===",
+        flagged,
+        entries.len()
+    );
+
+    Ok(())
+}
+
+/// Execute the 'oak audit conflicts' command: report every field (any
+/// field, not just controlled-vocabulary ones) with two or more distinct
+/// normalized values, optionally restricted to one scientific name.
+pub fn conflicts(db: &Database, scientific_name: Option<&str>) -> Result<()> {
+    let conflicts = db.find_conflicts(scientific_name)?;
+
+    eprintln!("=== Oak Conflict Report ===\n");
+
+    let mut last_name: Option<&str> = None;
+    for conflict in &conflicts {
+        if last_name != Some(conflict.scientific_name.as_str()) {
+            eprintln!("{}:", conflict.scientific_name);
+            last_name = Some(conflict.scientific_name.as_str());
+        }
+
+        let parts: Vec<String> = conflict
+            .variants
+            .iter()
+            .map(|(value, sources)| {
+                let citations: Vec<String> = sources
+                    .iter()
+                    .map(|(source_id, page_number)| match page_number {
+                        Some(page) => format!("{} p.{}", source_id, page),
+                        None => source_id.clone(),
+                    })
+                    .collect();
+                format!("'{}' ({})", value, citations.join(", "))
+            })
+            .collect();
+
+        eprintln!("  {}: {}", conflict.field_name, parts.join(" vs "));
+    }
+
+    eprintln!(
+        "\n=== Summary: {} conflicting field(s) ===",
+        conflicts.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use dialoguer::Password;
+use secrecy::SecretString;
+use std::path::Path;
+
+use crate::db::Database;
+
+/// Execute the 'oak export-backup' command
+pub fn export(db: &Database, out: &Path, passphrase: Option<String>) -> Result<()> {
+    let passphrase = resolve_passphrase(passphrase, "Backup passphrase")?;
+    db.export_encrypted_backup(out, &passphrase)?;
+    eprintln!("Wrote encrypted backup to {}", out.display());
+    Ok(())
+}
+
+/// Execute the 'oak import-backup' command
+pub fn import(db: &Database, file: &Path, passphrase: Option<String>) -> Result<()> {
+    let passphrase = resolve_passphrase(passphrase, "Backup passphrase")?;
+    db.import_encrypted_backup(file, &passphrase)?;
+    eprintln!("Restored backup from {}", file.display());
+    Ok(())
+}
+
+/// Use the passphrase given on the command line, or prompt for one if it
+/// wasn't (so it never has to appear in shell history).
+fn resolve_passphrase(passphrase: Option<String>, prompt: &str) -> Result<SecretString> {
+    let raw = match passphrase {
+        Some(p) => p,
+        None => Password::new().with_prompt(prompt).interact()?,
+    };
+    Ok(raw.into())
+}
@@ -0,0 +1,67 @@
+use anyhow::{bail, Result};
+use std::path::Path;
+
+use crate::db::Database;
+use crate::redb_repository::RedbRepository;
+use crate::repository::Repository;
+
+/// Storage backend a repository path can be opened as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Sqlite,
+    Redb,
+}
+
+/// Execute the 'oak db convert' command: stream every source and oak entry
+/// out of an existing repository and into a freshly created one of a
+/// possibly different backend.
+pub fn convert(from: Backend, from_path: &Path, to: Backend, to_path: &Path) -> Result<()> {
+    if !from_path.exists() {
+        bail!(
+            "Source database {} does not exist",
+            from_path.display()
+        );
+    }
+    if to_path.exists() {
+        bail!(
+            "Refusing to convert into {}: file already exists",
+            to_path.display()
+        );
+    }
+
+    let source_repo = open_repository(from, from_path)?;
+    let sources = source_repo.list_sources()?;
+    let oak_entries = source_repo.list_oak_entries()?;
+    let attributes = source_repo.list_attributes()?;
+
+    let target_repo = open_repository(to, to_path)?;
+    // Attributes first: bulk_import -> save_oak_entry rejects any attribute
+    // name the target doesn't already recognize.
+    for attribute in &attributes {
+        target_repo.register_attribute(attribute)?;
+    }
+    target_repo.bulk_import(&sources, &oak_entries)?;
+
+    eprintln!(
+        "Converted {} sources and {} oak entries from {:?} ({}) to {:?} ({})",
+        sources.len(),
+        oak_entries.len(),
+        from,
+        from_path.display(),
+        to,
+        to_path.display()
+    );
+    Ok(())
+}
+
+fn open_repository(backend: Backend, path: &Path) -> Result<Box<dyn Repository>> {
+    match backend {
+        Backend::Sqlite => {
+            let path_str = path
+                .to_str()
+                .ok_or_else(|| anyhow::anyhow!("Database path is not valid UTF-8"))?;
+            Ok(Box::new(Database::open(path_str, None)?))
+        }
+        Backend::Redb => Ok(Box::new(RedbRepository::open(path)?)),
+    }
+}
@@ -4,10 +4,24 @@ use crate::editor;
 use crate::schema::SchemaValidator;
 
 /// Execute the 'oak edit' command
-pub fn execute(db: &Database, validator: &SchemaValidator, name: &str) -> Result<()> {
+pub fn execute(db: &Database, validator: &SchemaValidator, name: &str, create: bool) -> Result<()> {
     // Get existing entry
-    let entry = db.get_oak_entry(name)?
-        .ok_or_else(|| anyhow!("Entry '{}' not found. Use 'oak new' to create it.", name))?;
+    let entry = match db.get_oak_entry(name)? {
+        Some(entry) => entry,
+        None if create => {
+            println!("Entry '{}' not found, creating it.", name);
+            let entry = editor::new_oak_entry(name, validator)?;
+            db.save_oak_entry(&entry)?;
+            println!("✓ Successfully created entry for '{}'", entry.scientific_name);
+            return Ok(());
+        }
+        None => {
+            return Err(anyhow!(
+                "Entry '{}' not found. Use 'oak new' or pass --create to create it.",
+                name
+            ))
+        }
+    };
 
     println!("Editing Oak entry: {}", name);
 
@@ -1,25 +1,32 @@
 use anyhow::{anyhow, Result};
 use crate::db::Database;
+use crate::search;
 
 /// Execute the 'oak find' command
-pub fn execute(db: &Database, query: &str, id_only: bool, search_type: &str) -> Result<()> {
+pub fn execute(db: &Database, query: &str, id_only: bool, search_type: &str, fuzzy: bool) -> Result<()> {
     let mut oak_results = Vec::new();
     let mut source_results = Vec::new();
 
     // Search based on type
     match search_type {
         "oak" => {
-            oak_results = db.search_oak_entries(query)?;
+            oak_results = search_oaks(db, query, fuzzy)?;
         }
         "source" => {
-            source_results = db.search_sources(query)?;
+            source_results = search_sources(db, query, fuzzy)?;
         }
         "both" => {
-            oak_results = db.search_oak_entries(query)?;
-            source_results = db.search_sources(query)?;
+            oak_results = search_oaks(db, query, fuzzy)?;
+            source_results = search_sources(db, query, fuzzy)?;
+        }
+        "fts" => {
+            return print_fts_results(db, query, id_only);
         }
         _ => {
-            return Err(anyhow!("Invalid search type '{}'. Must be 'oak', 'source', or 'both'.", search_type));
+            return Err(anyhow!(
+                "Invalid search type '{}'. Must be 'oak', 'source', 'both', or 'fts'.",
+                search_type
+            ));
         }
     }
 
@@ -56,3 +63,44 @@ pub fn execute(db: &Database, query: &str, id_only: bool, search_type: &str) ->
 
     Ok(())
 }
+
+/// Search oak entries, either by substring or (with `fuzzy`) typo-tolerant ranked matching.
+fn search_oaks(db: &Database, query: &str, fuzzy: bool) -> Result<Vec<String>> {
+    if fuzzy {
+        Ok(search::fuzzy_search_oaks(&db.list_oak_entries()?, query))
+    } else {
+        db.search_oak_entries(query)
+    }
+}
+
+/// Search sources, either by substring or (with `fuzzy`) typo-tolerant ranked matching.
+fn search_sources(db: &Database, query: &str, fuzzy: bool) -> Result<Vec<String>> {
+    if fuzzy {
+        Ok(search::fuzzy_search_sources(&db.list_sources()?, query))
+    } else {
+        db.search_sources(query)
+    }
+}
+
+/// Ranked full-text search over data point values and synonyms (`oak_fts`),
+/// best match first, with `<b>`-highlighted snippets.
+fn print_fts_results(db: &Database, query: &str, id_only: bool) -> Result<()> {
+    let hits = db.full_text_search(query)?;
+
+    if hits.is_empty() {
+        if !id_only {
+            eprintln!("No results found for query '{}'", query);
+        }
+        return Ok(());
+    }
+
+    for hit in &hits {
+        if id_only {
+            println!("{}", hit.scientific_name);
+        } else {
+            eprintln!("  {} ({}): {}", hit.scientific_name, hit.field_name, hit.snippet);
+        }
+    }
+
+    Ok(())
+}
@@ -1,13 +1,28 @@
 use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
 use dialoguer::Select;
 use std::fs;
 use std::path::Path;
 
+use crate::audit;
 use crate::db::Database;
 use crate::editor;
-use crate::models::{DataPoint, OakEntry};
+use crate::models::{DataPoint, FieldMergeRule, MergePolicy, Mergeable, OakEntry};
 use crate::schema::SchemaValidator;
 
+/// Prints an informational warning for every controlled-vocabulary field
+/// where `entry` now holds disagreeing values from different sources.
+fn warn_disagreements(validator: &SchemaValidator, entry: &OakEntry) {
+    let enumerated_fields = validator.get_enumerated_fields();
+    for d in audit::detect_disagreements(entry, &enumerated_fields) {
+        eprintln!(
+            "ℹ Sources disagree for '{}' — {}",
+            entry.scientific_name,
+            audit::format_disagreement(&d)
+        );
+    }
+}
+
 /// Represents a conflict between database and import data
 #[derive(Debug)]
 struct Conflict {
@@ -16,12 +31,31 @@ struct Conflict {
     import_value: String,
 }
 
+/// How to resolve an import record that collides with an existing `OakEntry`.
+///
+/// When unset, the existing interactive same-source conflict detection is used
+/// instead (see `detect_conflicts`/`handle_conflicts`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum CollisionStrategy {
+    /// Replace the existing entry wholesale with the imported one
+    Overwrite,
+    /// Leave the existing entry untouched
+    Skip,
+    /// Union datapoints (deduped on value+source) and merge synonyms
+    Merge,
+    /// Union datapoints (deduped on value+source) only; leave synonyms alone
+    AppendDatapoints,
+}
+
 /// Execute the 'oak import-bulk' command
 pub fn execute(
     db: &Database,
     validator: &SchemaValidator,
     file_path: &Path,
     source_id: &str,
+    collision: Option<CollisionStrategy>,
+    merge_policy: MergePolicy,
 ) -> Result<()> {
     // Verify source exists
     if db.get_source(source_id)?.is_none() {
@@ -55,11 +89,12 @@ pub fn execute(
         eprintln!("\nProcessing: {}", import_entry.scientific_name);
 
         // Validate the entry
-        if let Err(e) = validator.validate(&import_entry) {
-            eprintln!(
-                "⚠ Validation failed for '{}': {}",
-                import_entry.scientific_name, e
-            );
+        let report = validator.validate_verbose(&import_entry)?;
+        if !report.valid {
+            eprintln!("⚠ Validation failed for '{}':", import_entry.scientific_name);
+            for unit in &report.errors {
+                eprintln!("  - {} (at {})", unit.message, unit.instance_location);
+            }
             eprintln!("Skipping entry.");
             skipped_count += 1;
             continue;
@@ -69,13 +104,49 @@ pub fn execute(
         let existing_entry = db.get_oak_entry(&import_entry.scientific_name)?;
 
         if let Some(mut existing) = existing_entry {
-            // Detect conflicts
-            let conflicts = detect_conflicts(&existing, &import_entry, source_id);
+            if let Some(strategy) = collision {
+                // An explicit collision strategy bypasses interactive resolution.
+                match strategy {
+                    CollisionStrategy::Overwrite => {
+                        db.save_oak_entry(&import_entry)?;
+                        record_baselines(db, &import_entry, &import_entry, source_id)?;
+                        eprintln!("✓ Overwrote entry for '{}'", import_entry.scientific_name);
+                        updated_count += 1;
+                    }
+                    CollisionStrategy::Skip => {
+                        eprintln!("⊘ Skipped '{}' (already exists)", import_entry.scientific_name);
+                        skipped_count += 1;
+                    }
+                    CollisionStrategy::Merge => {
+                        existing.merge(&import_entry, &MergePolicy::UNION_ALL);
+                        db.save_oak_entry(&existing)?;
+                        record_baselines(db, &existing, &import_entry, source_id)?;
+                        warn_disagreements(validator, &existing);
+                        eprintln!("✓ Merged data for '{}'", existing.scientific_name);
+                        updated_count += 1;
+                    }
+                    CollisionStrategy::AppendDatapoints => {
+                        existing.merge_datapoint_fields(&import_entry, FieldMergeRule::UnionAll);
+                        db.save_oak_entry(&existing)?;
+                        record_baselines(db, &existing, &import_entry, source_id)?;
+                        warn_disagreements(validator, &existing);
+                        eprintln!("✓ Appended datapoints for '{}'", existing.scientific_name);
+                        updated_count += 1;
+                    }
+                }
+                continue;
+            }
+
+            // Three-way merge against the stored per-source baseline: fast-forwards
+            // are applied directly to `existing`; only genuine conflicts come back.
+            let conflicts = detect_conflicts(db, &mut existing, &import_entry, source_id)?;
 
             if conflicts.is_empty() {
                 // No conflicts, merge the data
-                merge_entries(&mut existing, &import_entry, source_id);
+                existing.merge(&import_entry, &merge_policy);
                 db.save_oak_entry(&existing)?;
+                record_baselines(db, &existing, &import_entry, source_id)?;
+                warn_disagreements(validator, &existing);
                 eprintln!("✓ Merged data for '{}'", existing.scientific_name);
                 updated_count += 1;
             } else {
@@ -93,9 +164,12 @@ pub fn execute(
                     &import_entry,
                     source_id,
                     &conflicts,
+                    &merge_policy,
                 )? {
                     ConflictResolution::Resolved(entry) => {
                         db.save_oak_entry(&entry)?;
+                        record_baselines(db, &entry, &import_entry, source_id)?;
+                        warn_disagreements(validator, &entry);
                         eprintln!("✓ Resolved conflicts and saved '{}'", entry.scientific_name);
                         updated_count += 1;
                     }
@@ -108,6 +182,7 @@ pub fn execute(
         } else {
             // New entry, just save it
             db.save_oak_entry(&import_entry)?;
+            record_baselines(db, &import_entry, &import_entry, source_id)?;
             eprintln!("✓ Created new entry for '{}'", import_entry.scientific_name);
             imported_count += 1;
         }
@@ -121,104 +196,100 @@ pub fn execute(
     Ok(())
 }
 
-/// Detect conflicts where the same source_id has different values
-fn detect_conflicts(existing: &OakEntry, import: &OakEntry, source_id: &str) -> Vec<Conflict> {
-    let mut conflicts = Vec::new();
+fn find_by_source<'a>(points: &'a [DataPoint], source_id: &str) -> Option<&'a DataPoint> {
+    points.iter().find(|dp| dp.source_id == source_id)
+}
 
-    // Helper to check conflicts for a field
-    let check_field = |field_name: &str,
-                       existing_points: &[DataPoint],
-                       import_points: &[DataPoint]|
-     -> Vec<Conflict> {
-        let mut field_conflicts = Vec::new();
-
-        // Find data points in import that have the same source_id as in existing
-        for import_dp in import_points {
-            if import_dp.source_id == source_id {
-                // Check if there's an existing data point with the same source_id
-                if let Some(existing_dp) =
-                    existing_points.iter().find(|dp| dp.source_id == source_id)
-                {
-                    if existing_dp.value != import_dp.value {
-                        field_conflicts.push(Conflict {
-                            field_name: field_name.to_string(),
-                            db_value: existing_dp.value.clone(),
-                            import_value: import_dp.value.clone(),
-                        });
-                    }
-                }
-            }
-        }
+/// Insert or replace the datapoint for `dp.source_id` in `points` with `dp`.
+fn set_value_for_source(points: &mut Vec<DataPoint>, dp: &DataPoint) {
+    if let Some(existing) = points.iter_mut().find(|p| p.source_id == dp.source_id) {
+        existing.value = dp.value.clone();
+        existing.page_number = dp.page_number.clone();
+    } else {
+        points.push(dp.clone());
+    }
+}
 
-        field_conflicts
-    };
+/// The three-way merge outcome for one field's value under a given source_id.
+enum MergeOutcome {
+    /// Accept the incoming value: either a clean fast-forward (ours == base)
+    /// or there was no recorded baseline and no existing value to preserve.
+    FastForward,
+    /// Nothing to do; the current value already reflects the latest known state.
+    NoChange,
+    /// Both sides changed since the baseline; needs interactive resolution.
+    Conflict,
+}
 
-    conflicts.extend(check_field(
-        "common_names",
-        &existing.common_names,
-        &import.common_names,
-    ));
-    conflicts.extend(check_field(
-        "leaf_color",
-        &existing.leaf_color,
-        &import.leaf_color,
-    ));
-    conflicts.extend(check_field(
-        "bud_shape",
-        &existing.bud_shape,
-        &import.bud_shape,
-    ));
-    conflicts.extend(check_field(
-        "leaf_shape",
-        &existing.leaf_shape,
-        &import.leaf_shape,
-    ));
-    conflicts.extend(check_field(
-        "bark_texture",
-        &existing.bark_texture,
-        &import.bark_texture,
-    ));
-    conflicts.extend(check_field("habitat", &existing.habitat, &import.habitat));
-    conflicts.extend(check_field(
-        "native_range",
-        &existing.native_range,
-        &import.native_range,
-    ));
-    conflicts.extend(check_field("height", &existing.height, &import.height));
-
-    conflicts
+/// Classifies a field's value against base (B, last baseline), ours (O, current
+/// DB value), and theirs (T, incoming import value), mirroring how a three-way
+/// tree merge distinguishes "one side changed" from "both sides diverged."
+fn three_way_merge(base: Option<&str>, ours: Option<&str>, theirs: &str) -> MergeOutcome {
+    if ours == Some(theirs) || base == Some(theirs) {
+        MergeOutcome::NoChange
+    } else if ours == base {
+        MergeOutcome::FastForward
+    } else {
+        MergeOutcome::Conflict
+    }
 }
 
-/// Merge import entry into existing entry (no conflicts)
-fn merge_entries(existing: &mut OakEntry, import: &OakEntry, _source_id: &str) {
-    // Helper to merge field data points
-    let merge_field = |existing_points: &mut Vec<DataPoint>, import_points: &[DataPoint]| {
-        for import_dp in import_points {
-            // Only add if source_id doesn't already exist
-            if !existing_points
-                .iter()
-                .any(|dp| dp.source_id == import_dp.source_id)
-            {
-                existing_points.push(import_dp.clone());
+/// Using the stored per-source baseline, compute the three-way merge for each
+/// field under `source_id`: fast-forwards are applied directly to `existing`,
+/// and only genuine conflicts (both sides diverged from the baseline) are
+/// returned for interactive resolution.
+fn detect_conflicts(
+    db: &Database,
+    existing: &mut OakEntry,
+    import: &OakEntry,
+    source_id: &str,
+) -> Result<Vec<Conflict>> {
+    let mut conflicts = Vec::new();
+
+    // Only fields the import actually asserts something for under `source_id`
+    // are relevant here; a field the import is silent on can't conflict.
+    for field_name in import.attributes.keys() {
+        let theirs_dp = match find_by_source(import.get_field(field_name), source_id) {
+            Some(dp) => dp.clone(),
+            None => continue,
+        };
+        let ours_value = find_by_source(existing.get_field(field_name), source_id)
+            .map(|dp| dp.value.clone());
+        let base = db.get_baseline(&existing.scientific_name, source_id, field_name)?;
+
+        match three_way_merge(base.as_deref(), ours_value.as_deref(), &theirs_dp.value) {
+            MergeOutcome::FastForward => {
+                set_value_for_source(existing.get_field_mut(field_name), &theirs_dp);
+            }
+            MergeOutcome::NoChange => {}
+            MergeOutcome::Conflict => {
+                conflicts.push(Conflict {
+                    field_name: field_name.clone(),
+                    db_value: ours_value.unwrap_or_default(),
+                    import_value: theirs_dp.value.clone(),
+                });
             }
         }
-    };
+    }
 
-    merge_field(&mut existing.common_names, &import.common_names);
-    merge_field(&mut existing.leaf_color, &import.leaf_color);
-    merge_field(&mut existing.bud_shape, &import.bud_shape);
-    merge_field(&mut existing.leaf_shape, &import.leaf_shape);
-    merge_field(&mut existing.bark_texture, &import.bark_texture);
-    merge_field(&mut existing.habitat, &import.habitat);
-    merge_field(&mut existing.native_range, &import.native_range);
-    merge_field(&mut existing.height, &import.height);
-
-    // Merge synonyms
-    for syn in &import.synonyms {
-        if !existing.synonyms.contains(syn) {
-            existing.synonyms.push(syn.clone());
-        }
+    Ok(conflicts)
+}
+
+/// After saving, records the final accepted value as the new baseline for
+/// every field the import asserted something for, so the next import can
+/// tell a real change apart from a resend of the same data.
+fn record_baselines(db: &Database, entry: &OakEntry, import: &OakEntry, source_id: &str) -> Result<()> {
+    for field_name in import.attributes.keys() {
+        let theirs_dp = match find_by_source(import.get_field(field_name), source_id) {
+            Some(dp) => dp,
+            None => continue,
+        };
+        let final_value = find_by_source(entry.get_field(field_name), source_id)
+            .map(|dp| dp.value.as_str())
+            .unwrap_or(&theirs_dp.value);
+        db.set_baseline(&entry.scientific_name, source_id, field_name, final_value)?;
     }
+    Ok(())
 }
 
 enum ConflictResolution {
@@ -234,6 +305,7 @@ fn handle_conflicts(
     import: &OakEntry,
     source_id: &str,
     conflicts: &[Conflict],
+    merge_policy: &MergePolicy,
 ) -> Result<ConflictResolution> {
     for conflict in conflicts {
         eprintln!(
@@ -274,9 +346,9 @@ fn handle_conflicts(
             2 => {
                 // Open editor for manual merge
                 eprintln!("Opening editor for manual resolution...");
-                let merged = editor::edit_oak_entry(existing, validator)?;
-                // After manual editing, save the non-conflicting data from import too
-                merge_entries(&mut merged.clone(), import, source_id);
+                let mut merged = editor::edit_oak_entry(existing, validator)?;
+                // After manual editing, merge in the non-conflicting data from import too
+                merged.merge(import, merge_policy);
                 return Ok(ConflictResolution::Resolved(merged));
             }
             3 => {
@@ -288,28 +360,18 @@ fn handle_conflicts(
     }
 
     // After resolving all conflicts, merge non-conflicting data
-    merge_entries(existing, import, source_id);
+    existing.merge(import, merge_policy);
 
     Ok(ConflictResolution::Resolved(existing.clone()))
 }
 
 /// Replace a field value for a specific source
 fn replace_field_value(entry: &mut OakEntry, field_name: &str, source_id: &str, new_value: &str) {
-    let update_field = |points: &mut Vec<DataPoint>| {
-        if let Some(dp) = points.iter_mut().find(|dp| dp.source_id == source_id) {
-            dp.value = new_value.to_string();
-        }
-    };
-
-    match field_name {
-        "common_names" => update_field(&mut entry.common_names),
-        "leaf_color" => update_field(&mut entry.leaf_color),
-        "bud_shape" => update_field(&mut entry.bud_shape),
-        "leaf_shape" => update_field(&mut entry.leaf_shape),
-        "bark_texture" => update_field(&mut entry.bark_texture),
-        "habitat" => update_field(&mut entry.habitat),
-        "native_range" => update_field(&mut entry.native_range),
-        "height" => update_field(&mut entry.height),
-        _ => {}
+    if let Some(dp) = entry
+        .get_field_mut(field_name)
+        .iter_mut()
+        .find(|dp| dp.source_id == source_id)
+    {
+        dp.value = new_value.to_string();
     }
 }
@@ -0,0 +1,113 @@
+use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet};
+
+use crate::db::Database;
+use crate::models::OakEntry;
+
+/// Execute the 'oak lint' command
+///
+/// Scans every `OakEntry` and `Source` for referential-integrity and
+/// data-quality problems, prints a grouped report to stderr, and returns an
+/// error (non-zero exit) if any error-level problem was found.
+pub fn execute(db: &Database) -> Result<()> {
+    let entries = db.list_oak_entries()?;
+    let sources = db.list_sources()?;
+    let source_ids: HashSet<&str> = sources.iter().map(|s| s.source_id.as_str()).collect();
+    let mut referenced_sources: HashSet<String> = HashSet::new();
+
+    let mut error_count = 0usize;
+    let mut warning_count = 0usize;
+
+    eprintln!("=== Oak Lint Report ===\n");
+
+    for entry in &entries {
+        let (entry_errors, entry_warnings) = lint_entry(entry, &source_ids, &mut referenced_sources);
+
+        if !entry_errors.is_empty() || !entry_warnings.is_empty() {
+            eprintln!("{}:", entry.scientific_name);
+            for e in &entry_errors {
+                eprintln!("  [error] {}", e);
+            }
+            for w in &entry_warnings {
+                eprintln!("  [warn]  {}", w);
+            }
+            eprintln!();
+        }
+
+        error_count += entry_errors.len();
+        warning_count += entry_warnings.len();
+    }
+
+    let orphans: Vec<_> = sources
+        .iter()
+        .filter(|s| !referenced_sources.contains(&s.source_id))
+        .collect();
+
+    if !orphans.is_empty() {
+        eprintln!("Orphan sources (never referenced by any data point):");
+        for s in &orphans {
+            eprintln!("  [warn]  {} ({})", s.source_id, s.name);
+        }
+        eprintln!();
+        warning_count += orphans.len();
+    }
+
+    eprintln!(
+        "=== Summary: {} error(s), {} warning(s) across {} entries, {} sources ===",
+        error_count,
+        warning_count,
+        entries.len(),
+        sources.len()
+    );
+
+    if error_count > 0 {
+        return Err(anyhow!("lint found {} error-level problem(s)", error_count));
+    }
+
+    Ok(())
+}
+
+/// Lints a single entry, recording every source_id it references along the way.
+fn lint_entry(
+    entry: &OakEntry,
+    source_ids: &HashSet<&str>,
+    referenced_sources: &mut HashSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let mut has_any_data = false;
+
+    for (field_name, points) in &entry.attributes {
+        has_any_data |= !points.is_empty();
+
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        for dp in points {
+            referenced_sources.insert(dp.source_id.clone());
+
+            if !source_ids.contains(dp.source_id.as_str()) {
+                errors.push(format!(
+                    "{}: dangling source_id '{}'",
+                    field_name, dp.source_id
+                ));
+            }
+
+            *seen.entry(dp.value.as_str()).or_insert(0) += 1;
+        }
+
+        for (value, count) in seen {
+            if count > 1 {
+                errors.push(format!(
+                    "{}: duplicate value '{}' ({} occurrences)",
+                    field_name, value, count
+                ));
+            }
+        }
+    }
+
+    if !has_any_data {
+        warnings.push("has a scientific_name but no attribute data".to_string());
+    }
+
+    (errors, warnings)
+}
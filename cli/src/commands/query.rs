@@ -0,0 +1,34 @@
+use anyhow::{anyhow, Result};
+
+use crate::db::Database;
+use crate::query;
+
+/// Execute the 'oak query' command: parse each `[entity attribute value]`
+/// clause, join them over the fact log, and print one line per result
+/// binding as `var=value` pairs.
+pub fn execute(db: &Database, clauses: &[String]) -> Result<()> {
+    let patterns = clauses
+        .iter()
+        .map(|clause| query::parse_pattern(clause).map_err(|e| anyhow!(e)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let results = db.query(&patterns)?;
+
+    if results.is_empty() {
+        eprintln!("No results.");
+        return Ok(());
+    }
+
+    for binding in &results {
+        let mut vars: Vec<&String> = binding.keys().collect();
+        vars.sort();
+        let line = vars
+            .iter()
+            .map(|v| format!("{}={}", v, binding[*v]))
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!("{}", line);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Result};
+
+use crate::db::Database;
+use crate::models::{DataPoint, OakEntry};
+
+/// Execute the 'oak reformat' command
+///
+/// Canonicalizes the ordering of every `OakEntry`'s synonyms and per-field
+/// `DataPoint` vectors and writes the canonical form back to the database, so
+/// any future YAML serialization of the data (e.g. via `oak edit`) produces
+/// minimal, deterministic diffs. `Source` has no orderable collections, so
+/// only `OakEntry` records need canonicalizing.
+///
+/// With `check`, nothing is written; the command only reports whether
+/// anything is out of canonical form, exiting non-zero if so.
+pub fn execute(db: &Database, check: bool) -> Result<()> {
+    let entries = db.list_oak_entries()?;
+    let mut changed = 0usize;
+
+    for entry in &entries {
+        let canonical = canonicalize_entry(entry);
+        if !is_canonical(entry, &canonical) {
+            changed += 1;
+            if check {
+                eprintln!("not canonical: {}", entry.scientific_name);
+            } else {
+                db.save_oak_entry(&canonical)?;
+                eprintln!("✓ Reformatted '{}'", canonical.scientific_name);
+            }
+        }
+    }
+
+    if check {
+        if changed > 0 {
+            return Err(anyhow!(
+                "{} entr{} not in canonical form",
+                changed,
+                if changed == 1 { "y is" } else { "ies are" }
+            ));
+        }
+        println!("All {} entries already in canonical form.", entries.len());
+    } else {
+        println!("Reformatted {} of {} entries.", changed, entries.len());
+    }
+
+    Ok(())
+}
+
+fn sort_key(dp: &DataPoint) -> (String, String, Option<String>) {
+    (dp.value.clone(), dp.source_id.clone(), dp.page_number.clone())
+}
+
+fn canonical_field(points: &[DataPoint]) -> Vec<DataPoint> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+    sorted
+}
+
+fn canonicalize_entry(entry: &OakEntry) -> OakEntry {
+    let mut canonical = OakEntry::new(entry.scientific_name.clone());
+
+    for (field_name, points) in &entry.attributes {
+        canonical.set_field(field_name, canonical_field(points));
+    }
+
+    canonical.synonyms = entry.synonyms.clone();
+    canonical.synonyms.sort();
+
+    canonical
+}
+
+/// Compares two entries via their canonical YAML form, which also normalizes
+/// whitespace and drops now-empty optional fields per the structs' existing
+/// `skip_serializing_if` attributes.
+fn is_canonical(entry: &OakEntry, canonical: &OakEntry) -> bool {
+    serde_yaml::to_string(entry).ok() == serde_yaml::to_string(canonical).ok()
+}
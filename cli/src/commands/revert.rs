@@ -0,0 +1,15 @@
+use anyhow::Result;
+
+use crate::db::Database;
+
+/// Execute the 'oak revert' command: retract every fact asserted by
+/// `source_id` after `at` (a unix timestamp). Facts retracted after `at`
+/// are not reinstated.
+pub fn execute(db: &Database, source_id: &str, at: i64) -> Result<()> {
+    let count = db.revert_source_since(source_id, at)?;
+    eprintln!(
+        "✓ Retracted {} fact(s) asserted by '{}' after {}",
+        count, source_id, at
+    );
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! Read-only HTTP/JSON API over the compendium, gated behind the `server` feature.
+
+use anyhow::Result;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use crate::db::Database;
+use crate::models::{OakEntry, Source};
+
+struct AppState {
+    db: Mutex<Database>,
+}
+
+/// Execute the 'oak serve' command: boot a read-only HTTP/JSON API over the compendium.
+pub async fn execute(db: Database, port: u16) -> Result<()> {
+    let state = Arc::new(AppState { db: Mutex::new(db) });
+
+    let app = Router::new()
+        .route("/oaks", get(list_oaks))
+        .route("/oaks/:scientific_name", get(get_oak))
+        .route("/sources/:id", get(get_source))
+        .route("/search", get(search))
+        .with_state(state);
+
+    let addr = format!("0.0.0.0:{}", port);
+    eprintln!("Listening on http://{}", addr);
+
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+async fn list_oaks(State(state): State<Arc<AppState>>) -> Result<Json<Vec<String>>, StatusCode> {
+    let db = state.db.lock().unwrap();
+    let names = db
+        .list_oak_entries()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|e| e.scientific_name)
+        .collect();
+    Ok(Json(names))
+}
+
+async fn get_oak(
+    State(state): State<Arc<AppState>>,
+    Path(scientific_name): Path<String>,
+) -> Result<Json<OakEntry>, StatusCode> {
+    let db = state.db.lock().unwrap();
+    match db.get_oak_entry(&scientific_name) {
+        Ok(Some(entry)) => Ok(Json(entry)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn get_source(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Source>, StatusCode> {
+    let db = state.db.lock().unwrap();
+    match db.get_source(&id) {
+        Ok(Some(source)) => Ok(Json(source)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    q: String,
+    #[serde(rename = "type", default = "default_search_type")]
+    search_type: String,
+}
+
+fn default_search_type() -> String {
+    "both".to_string()
+}
+
+#[derive(Serialize)]
+struct SearchResults {
+    oaks: Vec<String>,
+    sources: Vec<String>,
+}
+
+async fn search(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<SearchResults>, StatusCode> {
+    let db = state.db.lock().unwrap();
+    let mut oaks = Vec::new();
+    let mut sources = Vec::new();
+
+    if params.search_type == "oak" || params.search_type == "both" {
+        oaks = db
+            .search_oak_entries(&params.q)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    if params.search_type == "source" || params.search_type == "both" {
+        sources = db
+            .search_sources(&params.q)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    Ok(Json(SearchResults { oaks, sources }))
+}
@@ -22,10 +22,19 @@ pub fn new(db: &Database) -> Result<()> {
 }
 
 /// Execute the 'oak source edit' command
-pub fn edit(db: &Database, id: &str) -> Result<()> {
+pub fn edit(db: &Database, id: &str, create: bool) -> Result<()> {
     // Get existing source
-    let source = db.get_source(id)?
-        .ok_or_else(|| anyhow!("Source '{}' not found.", id))?;
+    let source = match db.get_source(id)? {
+        Some(source) => source,
+        None if create => {
+            eprintln!("Source '{}' not found, creating it.", id);
+            let source = editor::new_source_with_id(id.to_string())?;
+            db.insert_source(&source)?;
+            eprintln!("✓ Successfully created source '{}'", source.source_id);
+            return Ok(());
+        }
+        None => return Err(anyhow!("Source '{}' not found. Pass --create to create it.", id)),
+    };
 
     eprintln!("Editing source: {}", id);
 
@@ -1,78 +1,416 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use rusqlite::{params, Connection, OptionalExtension};
-use crate::models::{OakEntry, Source, DataPoint};
+use secrecy::{ExposeSecret, SecretString};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::models::{AttributeDef, DataPoint, FieldConflict, HistoryEntry, OakEntry, SearchHit, Source};
+use crate::query::{Fact, Pattern};
+
+/// Normalizes a value for `Database::find_conflicts`' equality check: trims
+/// surrounding whitespace and case-folds, so e.g. "White Oak" and "white oak"
+/// aren't flagged as a conflict while genuinely divergent values still are.
+fn normalize_conflict_value(value: &str) -> String {
+    value.trim().to_lowercase()
+}
+
+/// The current time as a unix timestamp, the unit `asserted_at`/`retracted_at`
+/// are stored in.
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
 
 /// Database repository implementing the abstraction layer for data access
 pub struct Database {
     conn: Connection,
 }
 
+/// Per-connection pragmas applied by `Database::open_with_options` before
+/// any schema migration runs.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionOptions {
+    /// `PRAGMA foreign_keys = ON`; otherwise `ON DELETE CASCADE` is parsed but never enforced.
+    pub enable_foreign_keys: bool,
+    /// `PRAGMA busy_timeout`, so a second `oak` process retries instead of failing immediately.
+    pub busy_timeout: Option<Duration>,
+    /// `PRAGMA journal_mode = WAL`, letting readers proceed during a write.
+    pub wal_mode: bool,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            enable_foreign_keys: true,
+            busy_timeout: Some(Duration::from_secs(5)),
+            wal_mode: false,
+        }
+    }
+}
+
+/// One versioned schema change, applied in its own transaction when the
+/// database's `PRAGMA user_version` is below `version`.
+struct Migration {
+    version: u32,
+    up: fn(&Connection) -> Result<()>,
+}
+
+/// Every migration this binary knows about, in ascending version order.
+/// `Database::open` applies whichever of these are newer than the database's
+/// current `user_version`, atomically and in order.
+const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, up: migrate_v1_base_schema },
+    Migration { version: 2, up: migrate_v2_full_text_search },
+    Migration { version: 3, up: migrate_v3_attribute_registry },
+];
+
+/// True if `data_points` already exists in the pre-migration-framework shape
+/// (no `asserted_at` column), which `CREATE TABLE IF NOT EXISTS` below would
+/// otherwise silently leave untouched.
+fn data_points_needs_upgrade(conn: &Connection) -> Result<bool> {
+    let mut stmt = conn.prepare("PRAGMA table_info(data_points)")?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(!columns.is_empty() && !columns.iter().any(|c| c == "asserted_at"))
+}
+
+/// Rebuilds legacy `data_points` under the current shape and carries its
+/// rows across (`ALTER TABLE` can't drop its old UNIQUE constraint).
+fn upgrade_legacy_data_points_table(conn: &Connection) -> Result<()> {
+    conn.execute("ALTER TABLE data_points RENAME TO data_points_legacy", [])?;
+
+    conn.execute(
+        "CREATE TABLE data_points (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            scientific_name TEXT NOT NULL,
+            field_name TEXT NOT NULL,
+            value TEXT NOT NULL,
+            source_id TEXT NOT NULL,
+            page_number TEXT,
+            asserted_at INTEGER NOT NULL,
+            retracted_at INTEGER,
+            FOREIGN KEY (scientific_name) REFERENCES oak_entries(scientific_name) ON DELETE CASCADE,
+            FOREIGN KEY (source_id) REFERENCES sources(source_id)
+        )",
+        [],
+    )?;
+
+    // The old schema never recorded assertion time, so backfill with now.
+    conn.execute(
+        "INSERT INTO data_points (scientific_name, field_name, value, source_id, page_number, asserted_at, retracted_at)
+         SELECT scientific_name, field_name, value, source_id, page_number, ?1, NULL
+         FROM data_points_legacy",
+        params![now_unix()],
+    )?;
+
+    conn.execute("DROP TABLE data_points_legacy", [])?;
+
+    Ok(())
+}
+
+/// v1: sources, oak_entries, the data_points fact log, and import_baselines.
+fn migrate_v1_base_schema(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sources (
+            source_id TEXT PRIMARY KEY,
+            source_type TEXT NOT NULL,
+            name TEXT NOT NULL,
+            author TEXT,
+            year INTEGER,
+            url TEXT,
+            isbn TEXT,
+            doi TEXT,
+            notes TEXT
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS oak_entries (
+            scientific_name TEXT PRIMARY KEY,
+            synonyms TEXT
+        )",
+        [],
+    )?;
+
+    if data_points_needs_upgrade(conn)? {
+        upgrade_legacy_data_points_table(conn)?;
+    }
+
+    // An append-only fact log: "changing" a value retracts the old row
+    // (sets retracted_at) and inserts a new one instead of updating in place.
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS data_points (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            scientific_name TEXT NOT NULL,
+            field_name TEXT NOT NULL,
+            value TEXT NOT NULL,
+            source_id TEXT NOT NULL,
+            page_number TEXT,
+            asserted_at INTEGER NOT NULL,
+            retracted_at INTEGER,
+            FOREIGN KEY (scientific_name) REFERENCES oak_entries(scientific_name) ON DELETE CASCADE,
+            FOREIGN KEY (source_id) REFERENCES sources(source_id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_data_points_name
+         ON data_points(scientific_name)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_data_points_source
+         ON data_points(source_id)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_data_points_active
+         ON data_points(scientific_name, field_name, retracted_at)",
+        [],
+    )?;
+
+    // Per-source baselines for import-bulk's three-way merge.
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS import_baselines (
+            scientific_name TEXT NOT NULL,
+            source_id TEXT NOT NULL,
+            field_name TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (scientific_name, source_id, field_name)
+        )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// v2: the `oak_fts` full-text index over data point values and synonyms,
+/// kept in sync with `data_points`/`oak_entries` by triggers rather than
+/// being rebuilt on every save, plus a one-time back-fill from existing rows.
+fn migrate_v2_full_text_search(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS oak_fts USING fts5(
+            scientific_name UNINDEXED,
+            field_name UNINDEXED,
+            value,
+            tokenize = 'unicode61 remove_diacritics 2'
+        )",
+        [],
+    )?;
+
+    // data_points rows are asserted via INSERT and retracted via an UPDATE
+    // of retracted_at (see `save_oak_entry`); rows are only deleted when
+    // their oak entry is deleted (ON DELETE CASCADE), covered by the third
+    // trigger below.
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS data_points_ai AFTER INSERT ON data_points
+         WHEN NEW.retracted_at IS NULL
+         BEGIN
+            INSERT INTO oak_fts(scientific_name, field_name, value)
+            VALUES (NEW.scientific_name, NEW.field_name, NEW.value);
+         END",
+        [],
+    )?;
+
+    // Re-derive the indexed row set for (scientific_name, field_name) from
+    // data_points instead of deleting by value, since a blind delete-by-value
+    // would also remove another still-active data point's matching row.
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS data_points_au AFTER UPDATE OF retracted_at ON data_points
+         WHEN OLD.retracted_at IS NULL AND NEW.retracted_at IS NOT NULL
+         BEGIN
+            DELETE FROM oak_fts
+            WHERE scientific_name = OLD.scientific_name
+              AND field_name = OLD.field_name;
+
+            INSERT INTO oak_fts(scientific_name, field_name, value)
+            SELECT DISTINCT scientific_name, field_name, value
+            FROM data_points
+            WHERE scientific_name = OLD.scientific_name
+              AND field_name = OLD.field_name
+              AND retracted_at IS NULL;
+         END",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS data_points_ad AFTER DELETE ON data_points
+         BEGIN
+            DELETE FROM oak_fts
+            WHERE scientific_name = OLD.scientific_name
+              AND field_name = OLD.field_name;
+
+            INSERT INTO oak_fts(scientific_name, field_name, value)
+            SELECT DISTINCT scientific_name, field_name, value
+            FROM data_points
+            WHERE scientific_name = OLD.scientific_name
+              AND field_name = OLD.field_name
+              AND retracted_at IS NULL;
+         END",
+        [],
+    )?;
+
+    // Synonyms live in oak_entries.synonyms as a JSON array rather than in
+    // data_points, so they're indexed as their own 'synonyms' rows under a
+    // trigger on that table instead.
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS oak_entries_ai AFTER INSERT ON oak_entries
+         BEGIN
+            INSERT INTO oak_fts(scientific_name, field_name, value)
+            SELECT NEW.scientific_name, 'synonyms', syn.value
+            FROM json_each(NEW.synonyms) AS syn;
+         END",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS oak_entries_au AFTER UPDATE OF synonyms ON oak_entries
+         BEGIN
+            DELETE FROM oak_fts
+            WHERE scientific_name = OLD.scientific_name AND field_name = 'synonyms';
+
+            INSERT INTO oak_fts(scientific_name, field_name, value)
+            SELECT NEW.scientific_name, 'synonyms', syn.value
+            FROM json_each(NEW.synonyms) AS syn;
+         END",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS oak_entries_ad AFTER DELETE ON oak_entries
+         BEGIN
+            DELETE FROM oak_fts WHERE scientific_name = OLD.scientific_name;
+         END",
+        [],
+    )?;
+
+    conn.execute(
+        "INSERT INTO oak_fts(scientific_name, field_name, value)
+         SELECT scientific_name, field_name, value
+         FROM data_points WHERE retracted_at IS NULL",
+        [],
+    )?;
+    conn.execute(
+        "INSERT INTO oak_fts(scientific_name, field_name, value)
+         SELECT oak_entries.scientific_name, 'synonyms', syn.value
+         FROM oak_entries, json_each(oak_entries.synonyms) AS syn",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// v3: the `attributes` registry, seeded with the field names that used to
+/// be `OakEntry`'s fixed struct fields, so existing data keeps validating
+/// once attribute fields become dynamic (see `OakEntry::attributes`).
+fn migrate_v3_attribute_registry(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS attributes (
+            name TEXT PRIMARY KEY,
+            display_label TEXT NOT NULL,
+            value_type TEXT NOT NULL,
+            cardinality TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    let seeds: &[(&str, &str, &str, &str)] = &[
+        ("common_names", "Common Names", "text", "many"),
+        ("leaf_color", "Leaf Color", "enum", "many"),
+        ("bud_shape", "Bud Shape", "enum", "many"),
+        ("leaf_shape", "Leaf Shape", "enum", "many"),
+        ("bark_texture", "Bark Texture", "enum", "many"),
+        ("habitat", "Habitat", "text", "many"),
+        ("native_range", "Native Range", "text", "many"),
+        ("height", "Height", "text", "many"),
+    ];
+    for (name, display_label, value_type, cardinality) in seeds {
+        conn.execute(
+            "INSERT OR IGNORE INTO attributes (name, display_label, value_type, cardinality)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![name, display_label, value_type, cardinality],
+        )?;
+    }
+
+    Ok(())
+}
+
 impl Database {
-    /// Create a new database connection and initialize schema
-    pub fn new(db_path: &str) -> Result<Self> {
+    /// Open a database connection and bring its schema up to date. If
+    /// `passphrase` is supplied, the connection is keyed via SQLCipher and
+    /// verified immediately so a wrong passphrase fails with a clear error.
+    pub fn open(db_path: &str, passphrase: Option<&SecretString>) -> Result<Self> {
+        Self::open_with_options(db_path, passphrase, ConnectionOptions::default())
+    }
+
+    /// Like `open`, but with explicit control over the per-connection
+    /// pragmas in `ConnectionOptions` instead of the defaults.
+    pub fn open_with_options(
+        db_path: &str,
+        passphrase: Option<&SecretString>,
+        options: ConnectionOptions,
+    ) -> Result<Self> {
         let conn = Connection::open(db_path)
             .context("Failed to open database")?;
 
+        if let Some(passphrase) = passphrase {
+            conn.pragma_update(None, "key", passphrase.expose_secret())
+                .context("Failed to set encryption key")?;
+            conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .context("Failed to unlock database: wrong passphrase, or not an encrypted database")?;
+        }
+
+        if options.enable_foreign_keys {
+            conn.pragma_update(None, "foreign_keys", "ON")
+                .context("Failed to enable foreign key enforcement")?;
+        }
+        if let Some(timeout) = options.busy_timeout {
+            conn.busy_timeout(timeout)
+                .context("Failed to set busy timeout")?;
+        }
+        if options.wal_mode {
+            conn.pragma_update(None, "journal_mode", "WAL")
+                .context("Failed to enable WAL journal mode")?;
+        }
+
         let db = Database { conn };
-        db.initialize_schema()?;
+        db.run_migrations()?;
         Ok(db)
     }
 
-    /// Initialize the database schema if it doesn't exist
-    fn initialize_schema(&self) -> Result<()> {
-        // Sources table
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS sources (
-                source_id TEXT PRIMARY KEY,
-                source_type TEXT NOT NULL,
-                name TEXT NOT NULL,
-                author TEXT,
-                year INTEGER,
-                url TEXT,
-                isbn TEXT,
-                doi TEXT,
-                notes TEXT
-            )",
-            [],
-        )?;
+    /// Applies every migration newer than `PRAGMA user_version`, each in its
+    /// own transaction. Refuses to open a database from a newer binary.
+    fn run_migrations(&self) -> Result<()> {
+        let current_version: u32 =
+            self.conn
+                .query_row("PRAGMA user_version", [], |row| row.get(0))?;
 
-        // Oak entries table
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS oak_entries (
-                scientific_name TEXT PRIMARY KEY,
-                synonyms TEXT
-            )",
-            [],
-        )?;
+        let latest_version = MIGRATIONS.iter().map(|m| m.version).max().unwrap_or(0);
 
-        // Data points table - stores all attributed data
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS data_points (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                scientific_name TEXT NOT NULL,
-                field_name TEXT NOT NULL,
-                value TEXT NOT NULL,
-                source_id TEXT NOT NULL,
-                page_number TEXT,
-                FOREIGN KEY (scientific_name) REFERENCES oak_entries(scientific_name) ON DELETE CASCADE,
-                FOREIGN KEY (source_id) REFERENCES sources(source_id),
-                UNIQUE(scientific_name, field_name, source_id)
-            )",
-            [],
-        )?;
-
-        // Create indexes for performance
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_data_points_name
-             ON data_points(scientific_name)",
-            [],
-        )?;
+        if current_version > latest_version {
+            return Err(anyhow::anyhow!(
+                "Database schema version {} is newer than this binary supports (up to {}); refusing to open it.",
+                current_version,
+                latest_version
+            ));
+        }
 
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_data_points_source
-             ON data_points(source_id)",
-            [],
-        )?;
+        for migration in MIGRATIONS.iter().filter(|m| m.version > current_version) {
+            let tx = self.conn.unchecked_transaction()?;
+            (migration.up)(&tx).with_context(|| {
+                format!("Migration to schema version {} failed", migration.version)
+            })?;
+            tx.execute(&format!("PRAGMA user_version = {}", migration.version), [])?;
+            tx.commit()?;
+        }
 
         Ok(())
     }
@@ -172,58 +510,83 @@ impl Database {
         Ok(sources)
     }
 
-    // ========== Oak Entry Operations ==========
+    // ========== Attribute Registry ==========
 
-    /// Insert or update a complete oak entry
-    pub fn save_oak_entry(&self, entry: &OakEntry) -> Result<()> {
-        let tx = self.conn.unchecked_transaction()?;
+    /// The set of attribute names an `OakEntry` is currently allowed to hold
+    /// data points under. Used by `save_oak_entry` to reject typos/unknown
+    /// fields before they're silently written as orphaned `data_points` rows.
+    fn attribute_names(&self) -> Result<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT name FROM attributes")?;
+        let names = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<HashSet<_>, _>>()?;
+        Ok(names)
+    }
 
-        // Insert or replace the main entry
-        tx.execute(
-            "INSERT OR REPLACE INTO oak_entries (scientific_name, synonyms)
-             VALUES (?1, ?2)",
-            params![
-                entry.scientific_name,
-                serde_json::to_string(&entry.synonyms)?,
-            ],
+    /// List every registered attribute, ordered by name.
+    pub fn list_attributes(&self) -> Result<Vec<AttributeDef>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, display_label, value_type, cardinality FROM attributes ORDER BY name",
         )?;
+        let attrs = stmt
+            .query_map([], |row| {
+                Ok(AttributeDef {
+                    name: row.get(0)?,
+                    display_label: row.get(1)?,
+                    value_type: row.get(2)?,
+                    cardinality: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(attrs)
+    }
 
-        // Helper function to save data points for a field
-        let save_field = |field_name: &str, data_points: &[DataPoint]| -> Result<()> {
-            // First, delete existing data points for this field
-            tx.execute(
-                "DELETE FROM data_points
-                 WHERE scientific_name = ?1 AND field_name = ?2",
-                params![entry.scientific_name, field_name],
-            )?;
+    /// Look up a single registered attribute by name.
+    pub fn get_attribute(&self, name: &str) -> Result<Option<AttributeDef>> {
+        self.conn
+            .query_row(
+                "SELECT name, display_label, value_type, cardinality FROM attributes WHERE name = ?1",
+                params![name],
+                |row| {
+                    Ok(AttributeDef {
+                        name: row.get(0)?,
+                        display_label: row.get(1)?,
+                        value_type: row.get(2)?,
+                        cardinality: row.get(3)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(Into::into)
+    }
 
-            // Insert new data points
-            for dp in data_points {
-                tx.execute(
-                    "INSERT INTO data_points
-                     (scientific_name, field_name, value, source_id, page_number)
-                     VALUES (?1, ?2, ?3, ?4, ?5)",
-                    params![
-                        entry.scientific_name,
-                        field_name,
-                        dp.value,
-                        dp.source_id,
-                        dp.page_number,
-                    ],
-                )?;
-            }
-            Ok(())
-        };
+    /// Register a new attribute (or update an existing one's label/type/
+    /// cardinality), making it a legal key in `OakEntry::attributes`.
+    pub fn register_attribute(&self, attribute: &AttributeDef) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO attributes (name, display_label, value_type, cardinality)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(name) DO UPDATE SET
+                display_label = excluded.display_label,
+                value_type = excluded.value_type,
+                cardinality = excluded.cardinality",
+            params![
+                attribute.name,
+                attribute.display_label,
+                attribute.value_type,
+                attribute.cardinality,
+            ],
+        )?;
+        Ok(())
+    }
 
-        save_field("common_names", &entry.common_names)?;
-        save_field("leaf_color", &entry.leaf_color)?;
-        save_field("bud_shape", &entry.bud_shape)?;
-        save_field("leaf_shape", &entry.leaf_shape)?;
-        save_field("bark_texture", &entry.bark_texture)?;
-        save_field("habitat", &entry.habitat)?;
-        save_field("native_range", &entry.native_range)?;
-        save_field("height", &entry.height)?;
+    // ========== Oak Entry Operations ==========
 
+    /// Insert or update a complete oak entry
+    pub fn save_oak_entry(&self, entry: &OakEntry) -> Result<()> {
+        let known_attributes = self.attribute_names()?;
+        let tx = self.conn.unchecked_transaction()?;
+        save_oak_entry_tx(&tx, entry, &known_attributes)?;
         tx.commit()?;
         Ok(())
     }
@@ -260,7 +623,8 @@ impl Database {
             let mut stmt = self.conn.prepare(
                 "SELECT value, source_id, page_number
                  FROM data_points
-                 WHERE scientific_name = ?1 AND field_name = ?2"
+                 WHERE scientific_name = ?1 AND field_name = ?2 AND retracted_at IS NULL
+                 ORDER BY value, source_id, page_number"
             )?;
 
             let points = stmt
@@ -276,19 +640,22 @@ impl Database {
             Ok(points)
         };
 
-        let entry = OakEntry {
-            scientific_name: scientific_name.to_string(),
-            synonyms,
-            common_names: load_field("common_names")?,
-            leaf_color: load_field("leaf_color")?,
-            bud_shape: load_field("bud_shape")?,
-            leaf_shape: load_field("leaf_shape")?,
-            bark_texture: load_field("bark_texture")?,
-            habitat: load_field("habitat")?,
-            native_range: load_field("native_range")?,
-            height: load_field("height")?,
+        let field_names: Vec<String> = {
+            let mut stmt = self.conn.prepare(
+                "SELECT DISTINCT field_name FROM data_points
+                 WHERE scientific_name = ?1 AND retracted_at IS NULL",
+            )?;
+            stmt.query_map(params![scientific_name], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?
         };
 
+        let mut entry = OakEntry::new(scientific_name.to_string());
+        entry.synonyms = synonyms;
+        for field_name in field_names {
+            let points = load_field(&field_name)?;
+            entry.set_field(&field_name, points);
+        }
+
         Ok(Some(entry))
     }
 
@@ -301,6 +668,26 @@ impl Database {
         Ok(())
     }
 
+    /// List every oak entry in the database (full records, not just names)
+    pub fn list_oak_entries(&self) -> Result<Vec<OakEntry>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT scientific_name FROM oak_entries ORDER BY scientific_name")?;
+
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        drop(stmt);
+
+        names
+            .into_iter()
+            .map(|name| {
+                self.get_oak_entry(&name)?
+                    .context("Oak entry disappeared while listing")
+            })
+            .collect()
+    }
+
     /// Search for oak entries by name pattern
     pub fn search_oak_entries(&self, query: &str) -> Result<Vec<String>> {
         let pattern = format!("%{}%", query);
@@ -333,12 +720,376 @@ impl Database {
         Ok(ids)
     }
 
+    // ========== Full-Text Search ==========
+
+    /// Ranked full-text search over data point values and synonyms via the
+    /// `oak_fts` FTS5 index, best match first. `query` is an FTS5 match
+    /// expression (e.g. `leaf OR bark`, `"blue-green"`).
+    pub fn full_text_search(&self, query: &str) -> Result<Vec<SearchHit>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT scientific_name, field_name,
+                    snippet(oak_fts, 2, '<b>', '</b>', '...', 10),
+                    bm25(oak_fts)
+             FROM oak_fts
+             WHERE oak_fts MATCH ?1
+             ORDER BY bm25(oak_fts)",
+        )?;
+
+        let hits = stmt
+            .query_map(params![query], |row| {
+                Ok(SearchHit {
+                    scientific_name: row.get(0)?,
+                    field_name: row.get(1)?,
+                    snippet: row.get(2)?,
+                    score: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Full-text search query failed")?;
+
+        Ok(hits)
+    }
+
+    // ========== Provenance Operations ==========
+
+    /// The full assertion/retraction log for `scientific_name`/`field_name`,
+    /// oldest first, reconstructing every fact ever asserted (and, if later
+    /// superseded or reverted, retracted) for that field.
+    pub fn history(&self, scientific_name: &str, field_name: &str) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT value, source_id, page_number, asserted_at, retracted_at
+             FROM data_points
+             WHERE scientific_name = ?1 AND field_name = ?2
+             ORDER BY asserted_at, id",
+        )?;
+
+        let entries = stmt
+            .query_map(params![scientific_name, field_name], |row| {
+                Ok(HistoryEntry {
+                    value: row.get(0)?,
+                    source_id: row.get(1)?,
+                    page_number: row.get(2)?,
+                    asserted_at: row.get(3)?,
+                    retracted_at: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
+    /// Retract every still-active fact asserted by `source_id` after `at`
+    /// (a unix timestamp). Facts that were retracted after `at` (for example
+    /// values an import superseded) are not reinstated. Returns the number of
+    /// facts retracted.
+    pub fn revert_source_since(&self, source_id: &str, at: i64) -> Result<usize> {
+        let now = now_unix();
+        let affected = self.conn.execute(
+            "UPDATE data_points
+             SET retracted_at = ?1
+             WHERE source_id = ?2 AND asserted_at > ?3 AND retracted_at IS NULL",
+            params![now, source_id, at],
+        )?;
+        Ok(affected)
+    }
+
+    // ========== Query Engine ==========
+
+    /// Every active (non-retracted) fact in the database, flattened to
+    /// `(entity, attribute, value, source_id)` tuples for the query engine.
+    fn all_facts(&self) -> Result<Vec<Fact>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT scientific_name, field_name, value, source_id
+             FROM data_points WHERE retracted_at IS NULL",
+        )?;
+
+        let facts = stmt
+            .query_map([], |row| {
+                Ok(Fact {
+                    entity: row.get(0)?,
+                    attribute: row.get(1)?,
+                    value: row.get(2)?,
+                    source_id: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts)
+    }
+
+    /// Evaluates a datalog-style pattern-match query over the fact log. See
+    /// `crate::query` for the join semantics.
+    pub fn query(&self, patterns: &[Pattern]) -> Result<Vec<HashMap<String, String>>> {
+        let facts = self.all_facts()?;
+        Ok(crate::query::evaluate(&facts, patterns))
+    }
+
+    // ========== Conflict Detection ==========
+
+    /// Finds every `(scientific_name, field_name)` pair (optionally
+    /// restricted to one entry) with two or more distinct normalized active
+    /// values, across any field (see `crate::audit::detect_disagreements`
+    /// for the controlled-vocabulary-only variant).
+    pub fn find_conflicts(&self, scientific_name: Option<&str>) -> Result<Vec<FieldConflict>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT scientific_name, field_name, value, source_id, page_number
+             FROM data_points
+             WHERE retracted_at IS NULL AND (?1 IS NULL OR scientific_name = ?1)
+             ORDER BY scientific_name, field_name",
+        )?;
+
+        let rows: Vec<(String, String, String, String, Option<String>)> = stmt
+            .query_map(params![scientific_name], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut groups: std::collections::BTreeMap<(String, String), Vec<(String, String, Option<String>)>> =
+            std::collections::BTreeMap::new();
+        for (name, field_name, value, source_id, page_number) in rows {
+            groups
+                .entry((name, field_name))
+                .or_default()
+                .push((value, source_id, page_number));
+        }
+
+        let mut conflicts = Vec::new();
+        for ((scientific_name, field_name), points) in groups {
+            let mut display_value: HashMap<String, String> = HashMap::new();
+            let mut by_normalized: std::collections::BTreeMap<String, Vec<(String, Option<String>)>> =
+                std::collections::BTreeMap::new();
+
+            for (value, source_id, page_number) in points {
+                let normalized = normalize_conflict_value(&value);
+                display_value
+                    .entry(normalized.clone())
+                    .or_insert_with(|| value.clone());
+                by_normalized
+                    .entry(normalized)
+                    .or_default()
+                    .push((source_id, page_number));
+            }
+
+            if by_normalized.len() > 1 {
+                let variants = by_normalized
+                    .into_iter()
+                    .map(|(normalized, sources)| (display_value[&normalized].clone(), sources))
+                    .collect();
+                conflicts.push(FieldConflict {
+                    scientific_name,
+                    field_name,
+                    variants,
+                });
+            }
+        }
+
+        Ok(conflicts)
+    }
+
+    // ========== Import Baseline Operations ==========
+
+    /// Get the last-accepted value imported from `source_id` for `field_name`
+    /// on `scientific_name`, if any import has ever touched it.
+    pub fn get_baseline(
+        &self,
+        scientific_name: &str,
+        source_id: &str,
+        field_name: &str,
+    ) -> Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT value FROM import_baselines
+                 WHERE scientific_name = ?1 AND source_id = ?2 AND field_name = ?3",
+                params![scientific_name, source_id, field_name],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to get import baseline")
+    }
+
+    /// Record `value` as the new baseline for `scientific_name`/`source_id`/`field_name`.
+    pub fn set_baseline(
+        &self,
+        scientific_name: &str,
+        source_id: &str,
+        field_name: &str,
+        value: &str,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO import_baselines (scientific_name, source_id, field_name, value)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(scientific_name, source_id, field_name)
+             DO UPDATE SET value = excluded.value",
+            params![scientific_name, source_id, field_name, value],
+        )?;
+        Ok(())
+    }
+
     /// Begin a transaction for bulk operations
     pub fn begin_transaction(&mut self) -> Result<Transaction> {
         Ok(Transaction {
             tx: Some(self.conn.unchecked_transaction()?),
         })
     }
+
+    // ========== Bulk Transfer ==========
+
+    /// Writes every source and oak entry into this database in one
+    /// transaction, for moving data between storage backends (see
+    /// `crate::repository::Repository`).
+    pub fn bulk_import(&self, sources: &[Source], oak_entries: &[OakEntry]) -> Result<()> {
+        let known_attributes = self.attribute_names()?;
+        let tx = self.conn.unchecked_transaction()?;
+
+        for source in sources {
+            if self.get_source(&source.source_id)?.is_some() {
+                self.update_source(source)?;
+            } else {
+                self.insert_source(source)?;
+            }
+        }
+        for entry in oak_entries {
+            save_oak_entry_tx(&tx, entry, &known_attributes)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    // ========== Encrypted Backup ==========
+
+    /// Serializes every source and oak entry into a single authenticated,
+    /// password-encrypted blob (see `crate::backup`).
+    pub fn export_encrypted_backup(&self, out_path: &std::path::Path, passphrase: &SecretString) -> Result<()> {
+        crate::backup::export(self, out_path, passphrase)
+    }
+
+    /// Decrypts a blob written by `export_encrypted_backup` and saves every
+    /// source and oak entry it contains into this database.
+    pub fn import_encrypted_backup(&self, in_path: &std::path::Path, passphrase: &SecretString) -> Result<()> {
+        crate::backup::import(self, in_path, passphrase)
+    }
+}
+
+/// The body of `Database::save_oak_entry`, taking an already-open
+/// transaction so `Database::bulk_import` can save many entries under one
+/// transaction instead of committing one per entry.
+fn save_oak_entry_tx(
+    tx: &rusqlite::Transaction,
+    entry: &OakEntry,
+    known_attributes: &HashSet<String>,
+) -> Result<()> {
+    for field_name in entry.attributes.keys() {
+        if !known_attributes.contains(field_name.as_str()) {
+            bail!(
+                "Unknown attribute '{}'; register it first (see Database::register_attribute)",
+                field_name
+            );
+        }
+    }
+
+    // Upsert, not INSERT OR REPLACE, so this fires the UPDATE trigger rather
+    // than a DELETE + INSERT that would wipe the entry's data_points index.
+    tx.execute(
+        "INSERT INTO oak_entries (scientific_name, synonyms)
+         VALUES (?1, ?2)
+         ON CONFLICT(scientific_name) DO UPDATE SET synonyms = excluded.synonyms",
+        params![
+            entry.scientific_name,
+            serde_json::to_string(&entry.synonyms)?,
+        ],
+    )?;
+
+    let now = now_unix();
+
+    // Reconciles a field's incoming data points against its currently
+    // active facts, retracting what's gone and asserting what's new.
+    let save_field = |field_name: &str, data_points: &[DataPoint]| -> Result<()> {
+        let active: Vec<(i64, DataPoint)> = {
+            let mut stmt = tx.prepare(
+                "SELECT id, value, source_id, page_number
+                 FROM data_points
+                 WHERE scientific_name = ?1 AND field_name = ?2 AND retracted_at IS NULL",
+            )?;
+            stmt.query_map(params![entry.scientific_name, field_name], |row| {
+                Ok((
+                    row.get(0)?,
+                    DataPoint {
+                        value: row.get(1)?,
+                        source_id: row.get(2)?,
+                        page_number: row.get(3)?,
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+        };
+
+        // Retract active facts that the incoming data no longer asserts.
+        for (id, fact) in &active {
+            let still_asserted = data_points.iter().any(|dp| {
+                dp.source_id == fact.source_id
+                    && dp.value == fact.value
+                    && dp.page_number == fact.page_number
+            });
+            if !still_asserted {
+                tx.execute(
+                    "UPDATE data_points SET retracted_at = ?1 WHERE id = ?2",
+                    params![now, id],
+                )?;
+            }
+        }
+
+        // Assert any incoming data point not already active.
+        for dp in data_points {
+            let already_active = active.iter().any(|(_, fact)| {
+                fact.source_id == dp.source_id
+                    && fact.value == dp.value
+                    && fact.page_number == dp.page_number
+            });
+            if !already_active {
+                tx.execute(
+                    "INSERT INTO data_points
+                     (scientific_name, field_name, value, source_id, page_number, asserted_at, retracted_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, NULL)",
+                    params![
+                        entry.scientific_name,
+                        field_name,
+                        dp.value,
+                        dp.source_id,
+                        dp.page_number,
+                        now,
+                    ],
+                )?;
+            }
+        }
+        Ok(())
+    };
+
+    // Also reconcile fields with active facts from a previous save that are
+    // now absent from `entry.attributes`, so deleted sections still retract.
+    let mut field_names: HashSet<String> = entry.attributes.keys().cloned().collect();
+    {
+        let mut stmt = tx.prepare(
+            "SELECT DISTINCT field_name FROM data_points
+             WHERE scientific_name = ?1 AND retracted_at IS NULL",
+        )?;
+        let existing = stmt
+            .query_map(params![entry.scientific_name], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        field_names.extend(existing);
+    }
+
+    for field_name in &field_names {
+        save_field(field_name, entry.get_field(field_name))?;
+    }
+
+    Ok(())
 }
 
 /// Transaction wrapper for bulk operations
@@ -363,3 +1114,129 @@ impl<'a> Transaction<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataPoint, OakEntry, Source};
+
+    /// Builds the table shape `initialize_schema` created before the
+    /// migration framework existed: no asserted_at/retracted_at, and a
+    /// UNIQUE(scientific_name, field_name, source_id) the fact log no
+    /// longer wants.
+    fn create_legacy_schema(conn: &Connection) {
+        conn.execute(
+            "CREATE TABLE sources (
+                source_id TEXT PRIMARY KEY,
+                source_type TEXT NOT NULL,
+                name TEXT NOT NULL,
+                author TEXT,
+                year INTEGER,
+                url TEXT,
+                isbn TEXT,
+                doi TEXT,
+                notes TEXT
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "CREATE TABLE oak_entries (
+                scientific_name TEXT PRIMARY KEY,
+                synonyms TEXT
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "CREATE TABLE data_points (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                scientific_name TEXT NOT NULL,
+                field_name TEXT NOT NULL,
+                value TEXT NOT NULL,
+                source_id TEXT NOT NULL,
+                page_number TEXT,
+                FOREIGN KEY (scientific_name) REFERENCES oak_entries(scientific_name) ON DELETE CASCADE,
+                FOREIGN KEY (source_id) REFERENCES sources(source_id),
+                UNIQUE(scientific_name, field_name, source_id)
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO oak_entries (scientific_name, synonyms) VALUES ('Quercus alba', '[]')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO sources (source_id, source_type, name) VALUES ('src1', 'book', 'Some Book')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO data_points (scientific_name, field_name, value, source_id, page_number)
+             VALUES ('Quercus alba', 'leaf_shape', 'lobed', 'src1', '42')",
+            [],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_open_migrates_legacy_schema_without_losing_data() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        {
+            let conn = Connection::open(file.path()).unwrap();
+            create_legacy_schema(&conn);
+        }
+
+        let db = Database::open(file.path().to_str().unwrap(), None).unwrap();
+
+        let entry = db.get_oak_entry("Quercus alba").unwrap().unwrap();
+        let data_points = entry.get_field("leaf_shape");
+        assert_eq!(data_points.len(), 1);
+        assert_eq!(data_points[0].value, "lobed");
+        assert_eq!(data_points[0].source_id, "src1");
+    }
+
+    #[test]
+    fn test_save_and_get_oak_entry_round_trips_through_eav_history() {
+        let db = Database::open(":memory:", None).unwrap();
+        db.insert_source(&Source::new(
+            "src1".to_string(),
+            "book".to_string(),
+            "Some Book".to_string(),
+        ))
+        .unwrap();
+
+        let mut entry = OakEntry::new("Quercus alba".to_string());
+        entry.set_field(
+            "leaf_shape",
+            vec![DataPoint {
+                value: "lobed".to_string(),
+                source_id: "src1".to_string(),
+                page_number: None,
+            }],
+        );
+        db.save_oak_entry(&entry).unwrap();
+
+        let loaded = db.get_oak_entry("Quercus alba").unwrap().unwrap();
+        assert_eq!(loaded.get_field("leaf_shape")[0].value, "lobed");
+
+        // Re-saving with a changed value should retract the old data point
+        // rather than delete it, so history survives.
+        entry.set_field(
+            "leaf_shape",
+            vec![DataPoint {
+                value: "smooth".to_string(),
+                source_id: "src1".to_string(),
+                page_number: None,
+            }],
+        );
+        db.save_oak_entry(&entry).unwrap();
+
+        let reloaded = db.get_oak_entry("Quercus alba").unwrap().unwrap();
+        let current = reloaded.get_field("leaf_shape");
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].value, "smooth");
+    }
+}
@@ -145,6 +145,16 @@ pub fn new_source() -> Result<Source> {
         .with_prompt("Source ID (unique identifier)")
         .interact_text()?;
 
+    new_source_with_id(source_id)
+}
+
+/// Create a new source entry interactively, with the source ID already fixed
+/// (used by `oak source edit --create` so the ID isn't asked for twice)
+pub fn new_source_with_id(source_id: String) -> Result<Source> {
+    use dialoguer::Input;
+
+    println!("Creating new source '{}'...\n", source_id);
+
     let source_type: String = Input::new()
         .with_prompt("Source Type (Book, Paper, Website, Observation, etc.)")
         .interact_text()?;
@@ -1,11 +1,18 @@
+mod audit;
+mod backup;
 mod db;
 mod editor;
 mod models;
+mod query;
+mod redb_repository;
+mod repository;
 mod schema;
+mod search;
 mod commands;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use secrecy::SecretString;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -21,6 +28,11 @@ struct Cli {
     #[arg(short, long, default_value = "schema/oak_schema.json")]
     schema: PathBuf,
 
+    /// Passphrase for a SQLCipher-encrypted database (if omitted, the
+    /// database is opened without a key; there is no prompt)
+    #[arg(long, env = "OAK_DB_PASSPHRASE")]
+    passphrase: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -37,6 +49,10 @@ enum Commands {
     Edit {
         /// Scientific name of the oak to edit
         name: String,
+
+        /// Create the entry if it doesn't already exist
+        #[arg(long)]
+        create: bool,
     },
 
     /// Delete an Oak entry
@@ -54,15 +70,27 @@ enum Commands {
         #[arg(short, long)]
         id_only: bool,
 
-        /// Search type: oak, source, or both
+        /// Search type: oak, source, both, or fts (ranked full-text search)
         #[arg(short = 't', long, default_value = "both")]
         search_type: String,
+
+        /// Typo-tolerant, ranked search instead of plain substring matching
+        #[arg(short, long)]
+        fuzzy: bool,
     },
 
     /// Manage sources
     #[command(subcommand)]
     Source(SourceCommands),
 
+    /// Manage the storage backend
+    #[command(subcommand)]
+    Db(DbCommands),
+
+    /// Manage the registry of attributes an Oak entry can hold data points for
+    #[command(subcommand)]
+    Attribute(AttributeCommands),
+
     /// Add a new enumeration value to a field
     AddValue {
         /// Field name (e.g., leaf_shape)
@@ -80,9 +108,176 @@ enum Commands {
         /// Source ID to attribute the data to
         #[arg(short, long)]
         source_id: String,
+
+        /// How to resolve entries that already exist (default: interactive conflict resolution)
+        #[arg(short, long, value_enum)]
+        collision: Option<commands::import_bulk::CollisionStrategy>,
+
+        /// How to reconcile attribute fields during a clean (non-collision) merge
+        #[arg(long, value_enum, default_value = "union")]
+        on_merge: OnMergeArg,
+    },
+
+    /// Validate referential integrity and data quality across the whole database
+    Lint,
+
+    /// Scan for cross-source disagreements; `oak audit conflicts` instead
+    /// reports conflicts in every field, grouped straight from the fact log
+    Audit {
+        #[command(subcommand)]
+        action: Option<AuditCommands>,
+    },
+
+    /// Canonicalize the stored ordering of every Oak entry's synonyms and data points
+    Reformat {
+        /// Only report whether anything is out of canonical form; don't write
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Run a datalog-style pattern-match query over the entity/attribute/
+    /// value/source fact log
+    Query {
+        /// One or more `[entity attribute value]` clauses (a 4th term, if
+        /// present, matches source_id); `?name` binds a variable, `_` is a
+        /// wildcard, anything else is a literal
+        #[arg(required = true)]
+        clauses: Vec<String>,
+    },
+
+    /// Retract every fact asserted by a source after a given point in time
+    Revert {
+        /// Source ID whose recent assertions should be retracted
+        #[arg(short, long)]
+        source: String,
+
+        /// Unix timestamp (seconds); facts asserted after this time are retracted
+        #[arg(long)]
+        at: i64,
+    },
+
+    /// Export every source and oak entry into a single encrypted backup file
+    ExportBackup {
+        /// Path to write the backup to
+        out: PathBuf,
+
+        /// Passphrase to encrypt the backup with (prompted for if omitted)
+        #[arg(long)]
+        passphrase: Option<String>,
+    },
+
+    /// Restore sources and oak entries from a backup written by `export-backup`
+    ImportBackup {
+        /// Path to the backup file
+        file: PathBuf,
+
+        /// Passphrase the backup was encrypted with (prompted for if omitted)
+        #[arg(long)]
+        passphrase: Option<String>,
+    },
+
+    /// Start a read-only HTTP/JSON API server over the compendium
+    #[cfg(feature = "server")]
+    Serve {
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+    },
+}
+
+/// CLI-facing subset of `models::FieldMergeRule` for clean (non-collision) merges.
+///
+/// `union` maps to `FieldMergeRule::UnionBySource`, preserving the compendium's
+/// long-standing default of keeping at most one data point per source. The
+/// looser `FieldMergeRule::UnionAll` is only reachable via `--collision merge`
+/// or `--collision append-datapoints`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+enum OnMergeArg {
+    PreferIncoming,
+    PreferExisting,
+    Union,
+}
+
+impl From<OnMergeArg> for models::MergePolicy {
+    fn from(arg: OnMergeArg) -> Self {
+        match arg {
+            OnMergeArg::PreferIncoming => models::MergePolicy::PREFER_INCOMING,
+            OnMergeArg::PreferExisting => models::MergePolicy::PREFER_EXISTING,
+            OnMergeArg::Union => models::MergePolicy::UNION_BY_SOURCE,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum AuditCommands {
+    /// Report every (scientific_name, field_name) with two or more distinct
+    /// normalized values, across all fields
+    Conflicts {
+        /// Restrict the report to a single scientific name
+        name: Option<String>,
     },
 }
 
+#[derive(Subcommand)]
+enum DbCommands {
+    /// Stream every source and oak entry from one storage backend into a
+    /// freshly created one of another (or the same) kind
+    Convert {
+        /// Backend the source database is stored in
+        #[arg(long, value_enum)]
+        from: BackendArg,
+
+        /// Path to the existing source database
+        from_path: PathBuf,
+
+        /// Backend to write the converted database as
+        #[arg(long, value_enum)]
+        to: BackendArg,
+
+        /// Path to create the converted database at
+        to_path: PathBuf,
+    },
+}
+
+/// CLI-facing subset of `commands::db::Backend`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+enum BackendArg {
+    Sqlite,
+    Redb,
+}
+
+impl From<BackendArg> for commands::db::Backend {
+    fn from(arg: BackendArg) -> Self {
+        match arg {
+            BackendArg::Sqlite => commands::db::Backend::Sqlite,
+            BackendArg::Redb => commands::db::Backend::Redb,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum AttributeCommands {
+    /// Register a new attribute, or update an existing one's label/type/cardinality
+    Add {
+        /// The `OakEntry::attributes` key this definition governs, e.g. "leaf_shape"
+        name: String,
+
+        /// Human-readable label for UIs/reports, e.g. "Leaf Shape"
+        display_label: String,
+
+        /// Free-form kind of value, e.g. "enum", "text", "number"
+        value_type: String,
+
+        /// Free-form cardinality hint, e.g. "single", "many"
+        cardinality: String,
+    },
+
+    /// List all registered attributes
+    List,
+}
+
 #[derive(Subcommand)]
 enum SourceCommands {
     /// Create a new source
@@ -92,6 +287,10 @@ enum SourceCommands {
     Edit {
         /// Source ID to edit
         id: String,
+
+        /// Create the source if it doesn't already exist
+        #[arg(long)]
+        create: bool,
     },
 
     /// List all sources
@@ -102,38 +301,83 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     // Initialize database and schema
-    let db = db::Database::new(cli.database.to_str().unwrap())?;
+    let db_passphrase = cli.passphrase.clone().map(SecretString::from);
+    let db = db::Database::open(cli.database.to_str().unwrap(), db_passphrase.as_ref())?;
     let schema = schema::SchemaValidator::from_file(&cli.schema)?;
 
     match cli.command {
         Commands::New { name } => {
             commands::new::execute(&db, &schema, &name)?;
         }
-        Commands::Edit { name } => {
-            commands::edit::execute(&db, &schema, &name)?;
+        Commands::Edit { name, create } => {
+            commands::edit::execute(&db, &schema, &name, create)?;
         }
         Commands::Delete { name } => {
             commands::delete::execute(&db, &name)?;
         }
-        Commands::Find { query, id_only, search_type } => {
-            commands::find::execute(&db, &query, id_only, &search_type)?;
+        Commands::Find { query, id_only, search_type, fuzzy } => {
+            commands::find::execute(&db, &query, id_only, &search_type, fuzzy)?;
         }
         Commands::Source(source_cmd) => match source_cmd {
             SourceCommands::New => {
                 commands::source::new(&db)?;
             }
-            SourceCommands::Edit { id } => {
-                commands::source::edit(&db, &id)?;
+            SourceCommands::Edit { id, create } => {
+                commands::source::edit(&db, &id, create)?;
             }
             SourceCommands::List => {
                 commands::source::list(&db)?;
             }
         },
+        Commands::Db(db_cmd) => match db_cmd {
+            DbCommands::Convert { from, from_path, to, to_path } => {
+                commands::db::convert(from.into(), &from_path, to.into(), &to_path)?;
+            }
+        },
+        Commands::Attribute(attribute_cmd) => match attribute_cmd {
+            AttributeCommands::Add { name, display_label, value_type, cardinality } => {
+                commands::attribute::add(&db, &name, &display_label, &value_type, &cardinality)?;
+            }
+            AttributeCommands::List => {
+                commands::attribute::list(&db)?;
+            }
+        },
         Commands::AddValue { field, value } => {
             commands::add_value::execute(&cli.schema, &schema, &field, &value)?;
         }
-        Commands::ImportBulk { file, source_id } => {
-            commands::import_bulk::execute(&db, &schema, &file, &source_id)?;
+        Commands::ImportBulk { file, source_id, collision, on_merge } => {
+            commands::import_bulk::execute(&db, &schema, &file, &source_id, collision, on_merge.into())?;
+        }
+        Commands::Lint => {
+            commands::lint::execute(&db)?;
+        }
+        Commands::Audit { action } => match action {
+            None => {
+                commands::audit::execute(&db, &schema)?;
+            }
+            Some(AuditCommands::Conflicts { name }) => {
+                commands::audit::conflicts(&db, name.as_deref())?;
+            }
+        },
+        Commands::Reformat { check } => {
+            commands::reformat::execute(&db, check)?;
+        }
+        Commands::Query { clauses } => {
+            commands::query::execute(&db, &clauses)?;
+        }
+        Commands::Revert { source, at } => {
+            commands::revert::execute(&db, &source, at)?;
+        }
+        Commands::ExportBackup { out, passphrase } => {
+            commands::backup::export(&db, &out, passphrase)?;
+        }
+        Commands::ImportBackup { file, passphrase } => {
+            commands::backup::import(&db, &file, passphrase)?;
+        }
+        #[cfg(feature = "server")]
+        Commands::Serve { port } => {
+            let rt = tokio::runtime::Runtime::new()?;
+            rt.block_on(commands::serve::execute(db, port))?;
         }
     }
 
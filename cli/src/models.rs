@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Represents a single data point attributed to a specific source
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,43 +13,52 @@ pub struct DataPoint {
     pub page_number: Option<String>,
 }
 
+/// One ranked hit from `Database::full_text_search`, backed by the
+/// `oak_fts` FTS5 index.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub scientific_name: String,
+    pub field_name: String,
+    /// A `snippet(oak_fts, ...)` excerpt of the matching value, with
+    /// `<b>`-delimited highlights around matched terms
+    pub snippet: String,
+    /// The FTS5 `bm25()` weight; lower (more negative) means a better match
+    pub score: f64,
+}
+
+/// One row of a field's assertion/retraction history, as returned by
+/// `Database::history`. Mirrors a `data_points` fact: `retracted_at` is
+/// `None` for every fact that is currently active.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEntry {
+    pub value: String,
+    pub source_id: String,
+    pub page_number: Option<String>,
+    /// Unix timestamp when this fact was asserted
+    pub asserted_at: i64,
+    /// Unix timestamp when this fact was retracted, if it has been
+    pub retracted_at: Option<i64>,
+}
+
 /// Represents an Oak taxonomic entry
+///
+/// Attribute fields (`leaf_color`, `habitat`, `height`, ...) are no longer
+/// fixed struct fields: `attributes` holds one `Vec<DataPoint>` per field
+/// name, validated against the database's `attributes` registry on save, so
+/// a new observable trait is added by registering it there instead of
+/// editing this struct. It's a `BTreeMap` rather than a `HashMap` so
+/// serialization (and therefore `oak reformat`'s canonical form) is
+/// deterministic. `#[serde(flatten)]` keeps the on-disk/YAML shape
+/// unchanged: field names still appear as top-level keys alongside
+/// `scientific_name` and `synonyms`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OakEntry {
     /// Primary key: Scientific name
     pub scientific_name: String,
 
-    /// Common names
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub common_names: Vec<DataPoint>,
-
-    /// Leaf color
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub leaf_color: Vec<DataPoint>,
-
-    /// Bud shape
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub bud_shape: Vec<DataPoint>,
-
-    /// Leaf shape
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub leaf_shape: Vec<DataPoint>,
-
-    /// Bark texture
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub bark_texture: Vec<DataPoint>,
-
-    /// Habitat
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub habitat: Vec<DataPoint>,
-
-    /// Native range
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub native_range: Vec<DataPoint>,
-
-    /// Height range
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub height: Vec<DataPoint>,
+    /// Attribute field name -> its data points, e.g. `"leaf_shape"` -> `[...]`
+    #[serde(flatten)]
+    pub attributes: BTreeMap<String, Vec<DataPoint>>,
 
     /// Synonyms
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -60,17 +70,142 @@ impl OakEntry {
     pub fn new(scientific_name: String) -> Self {
         Self {
             scientific_name,
-            common_names: Vec::new(),
-            leaf_color: Vec::new(),
-            bud_shape: Vec::new(),
-            leaf_shape: Vec::new(),
-            bark_texture: Vec::new(),
-            habitat: Vec::new(),
-            native_range: Vec::new(),
-            height: Vec::new(),
+            attributes: BTreeMap::new(),
             synonyms: Vec::new(),
         }
     }
+
+    /// The data points for `field_name`, or an empty slice if the entry has
+    /// none (equivalent to the field never having been set).
+    pub fn get_field(&self, field_name: &str) -> &[DataPoint] {
+        self.attributes.get(field_name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Sets `field_name`'s data points, removing the field entirely if
+    /// `data_points` is empty so it doesn't linger as an empty entry (and
+    /// doesn't get serialized, matching the old per-field
+    /// `skip_serializing_if` behavior).
+    pub fn set_field(&mut self, field_name: &str, data_points: Vec<DataPoint>) {
+        if data_points.is_empty() {
+            self.attributes.remove(field_name);
+        } else {
+            self.attributes.insert(field_name.to_string(), data_points);
+        }
+    }
+
+    /// Mutable access to `field_name`'s data points, creating an empty entry
+    /// for it if absent. Callers that push/modify in place should prefer
+    /// `set_field` when they already have a whole `Vec<DataPoint>` to hand
+    /// over instead; this is for editing individual data points.
+    pub fn get_field_mut(&mut self, field_name: &str) -> &mut Vec<DataPoint> {
+        self.attributes.entry(field_name.to_string()).or_default()
+    }
+}
+
+/// How a single `Vec<DataPoint>` field reconciles when merging one `OakEntry`
+/// into another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldMergeRule {
+    /// Keep the existing side's data points for this field untouched
+    PreferExisting,
+    /// Replace the existing side's data points for this field with the incoming side's
+    PreferIncoming,
+    /// Union data points, keeping at most one per source_id (the existing behavior)
+    UnionBySource,
+    /// Union data points, keeping every distinct (value, source_id) pair from both sides
+    UnionAll,
+}
+
+/// Which `FieldMergeRule` a `Mergeable::merge` call should apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergePolicy {
+    pub rule: FieldMergeRule,
+}
+
+impl MergePolicy {
+    pub const PREFER_EXISTING: Self = Self { rule: FieldMergeRule::PreferExisting };
+    pub const PREFER_INCOMING: Self = Self { rule: FieldMergeRule::PreferIncoming };
+    pub const UNION_BY_SOURCE: Self = Self { rule: FieldMergeRule::UnionBySource };
+    pub const UNION_ALL: Self = Self { rule: FieldMergeRule::UnionAll };
+}
+
+/// Types that can merge another value of the same type into themselves, with
+/// `self` taking precedence on scalar identity fields and `policy` governing
+/// how collection fields reconcile.
+pub trait Mergeable {
+    fn merge(&mut self, other: &Self, policy: &MergePolicy);
+}
+
+fn merge_field(ours: &[DataPoint], theirs: &[DataPoint], rule: FieldMergeRule) -> Vec<DataPoint> {
+    match rule {
+        FieldMergeRule::PreferExisting => ours.to_vec(),
+        FieldMergeRule::PreferIncoming => theirs.to_vec(),
+        FieldMergeRule::UnionBySource => {
+            let mut merged = ours.to_vec();
+            for dp in theirs {
+                if !merged.iter().any(|existing| existing.source_id == dp.source_id) {
+                    merged.push(dp.clone());
+                }
+            }
+            merged
+        }
+        FieldMergeRule::UnionAll => {
+            let mut merged = ours.to_vec();
+            for dp in theirs {
+                let already_present = merged
+                    .iter()
+                    .any(|existing| existing.value == dp.value && existing.source_id == dp.source_id);
+                if !already_present {
+                    merged.push(dp.clone());
+                }
+            }
+            merged
+        }
+    }
+}
+
+fn merge_synonyms_into(ours: &mut Vec<String>, theirs: &[String]) {
+    for syn in theirs {
+        if !ours.contains(syn) {
+            ours.push(syn.clone());
+        }
+    }
+}
+
+impl OakEntry {
+    /// Merges only the attribute datapoint fields (not synonyms) using `rule`.
+    pub fn merge_datapoint_fields(&mut self, other: &Self, rule: FieldMergeRule) {
+        let field_names: Vec<String> = self
+            .attributes
+            .keys()
+            .chain(other.attributes.keys())
+            .cloned()
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        for field_name in field_names {
+            let merged = merge_field(self.get_field(&field_name), other.get_field(&field_name), rule);
+            self.set_field(&field_name, merged);
+        }
+    }
+}
+
+impl Mergeable for OakEntry {
+    /// Merges `other` into `self`: `scientific_name` is the identity field and
+    /// always wins, attribute fields follow `policy.rule`, and synonyms are
+    /// unioned unless `policy` says to prefer one side outright.
+    fn merge(&mut self, other: &Self, policy: &MergePolicy) {
+        self.merge_datapoint_fields(other, policy.rule);
+
+        match policy.rule {
+            FieldMergeRule::PreferExisting => {}
+            FieldMergeRule::PreferIncoming => self.synonyms = other.synonyms.clone(),
+            FieldMergeRule::UnionBySource | FieldMergeRule::UnionAll => {
+                merge_synonyms_into(&mut self.synonyms, &other.synonyms)
+            }
+        }
+    }
 }
 
 /// Represents a source reference
@@ -126,3 +261,36 @@ impl Source {
         }
     }
 }
+
+/// One row of the database's `attributes` registry: the definition of an
+/// observable trait an `OakEntry` can hold data points for under
+/// `OakEntry::attributes`. `value_type`/`cardinality` are free-form strings
+/// (like `Source::source_type`) rather than enums, since they're
+/// informational hints for tooling, not something this binary branches on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeDef {
+    /// The `OakEntry::attributes` key this definition governs, e.g. `"leaf_shape"`
+    pub name: String,
+    /// Human-readable label for UIs/reports, e.g. `"Leaf Shape"`
+    pub display_label: String,
+    /// Free-form kind of value, e.g. `"enum"`, `"text"`, `"number"`
+    pub value_type: String,
+    /// Free-form cardinality hint, e.g. `"single"`, `"many"`
+    pub cardinality: String,
+}
+
+/// One `(scientific_name, field_name)` pair where two or more distinct
+/// normalized values are each backed by active data points, as returned by
+/// `Database::find_conflicts`. Unlike `audit::Disagreement` (which only
+/// looks at controlled-vocabulary fields already loaded into an
+/// `OakEntry`), this groups directly over the `data_points` fact log across
+/// every field, so it also catches divergence in free-text fields like
+/// height or native_range.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldConflict {
+    pub scientific_name: String,
+    pub field_name: String,
+    /// Each distinct normalized value (shown in its first-seen original
+    /// casing), with every `(source_id, page_number)` asserting it
+    pub variants: Vec<(String, Vec<(String, Option<String>)>)>,
+}
@@ -0,0 +1,200 @@
+//! A small datalog-style pattern-matching engine over the entity/attribute/
+//! value/source fact log (`entity` = scientific_name, `attribute` =
+//! field_name), letting callers ask relational questions like "every
+//! scientific_name whose leaf_shape is lobed and whose native_range is
+//! attested by source X" without bespoke code per question.
+
+use std::collections::HashMap;
+
+/// One position in a `Pattern`: a bound literal, a variable to bind, or a
+/// wildcard that matches anything without binding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    Lit(String),
+    Var(String),
+    Any,
+}
+
+impl Term {
+    fn parse(tok: &str) -> Term {
+        if tok == "_" {
+            Term::Any
+        } else if let Some(name) = tok.strip_prefix('?') {
+            Term::Var(name.to_string())
+        } else {
+            Term::Lit(tok.to_string())
+        }
+    }
+}
+
+/// One `(entity, attribute, value, source_id)` fact from the data_points log.
+#[derive(Debug, Clone)]
+pub struct Fact {
+    pub entity: String,
+    pub attribute: String,
+    pub value: String,
+    pub source_id: String,
+}
+
+/// A single query clause matched against every `Fact`.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pub entity: Term,
+    pub attribute: Term,
+    pub value: Term,
+    pub source: Term,
+}
+
+/// Parses the compact `[?e leaf_shape lobed]` textual syntax into a
+/// `Pattern`. A trailing fourth term, if present, matches `source_id`;
+/// otherwise the source is left unbound (`Term::Any`). A token starting with
+/// `?` is a variable, a bare `_` is a wildcard, anything else is a literal.
+pub fn parse_pattern(text: &str) -> Result<Pattern, String> {
+    let inner = text
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| format!("pattern '{}' must be wrapped in [...]", text))?;
+
+    let tokens: Vec<&str> = inner.split_whitespace().collect();
+    match tokens.as_slice() {
+        [e, a, v] => Ok(Pattern {
+            entity: Term::parse(e),
+            attribute: Term::parse(a),
+            value: Term::parse(v),
+            source: Term::Any,
+        }),
+        [e, a, v, s] => Ok(Pattern {
+            entity: Term::parse(e),
+            attribute: Term::parse(a),
+            value: Term::parse(v),
+            source: Term::parse(s),
+        }),
+        _ => Err(format!(
+            "pattern '{}' must have 3 or 4 terms: [entity attribute value [source]]",
+            text
+        )),
+    }
+}
+
+/// Checks `term` against `value` under the current `bindings`, returning
+/// `None` on a mismatch, `Some(None)` on a match that introduces no new
+/// binding, or `Some(Some((var, value)))` on a match that binds a new variable.
+fn term_matches(
+    term: &Term,
+    value: &str,
+    bindings: &HashMap<String, String>,
+) -> Option<Option<(String, String)>> {
+    match term {
+        Term::Any => Some(None),
+        Term::Lit(lit) => (lit == value).then_some(None),
+        Term::Var(name) => match bindings.get(name) {
+            Some(bound) if bound == value => Some(None),
+            Some(_) => None,
+            None => Some(Some((name.clone(), value.to_string()))),
+        },
+    }
+}
+
+/// Tries to extend `binding` with `fact` under `pattern`, returning the
+/// extended binding on a match or `None` if any term conflicts.
+fn try_extend(
+    binding: &HashMap<String, String>,
+    pattern: &Pattern,
+    fact: &Fact,
+) -> Option<HashMap<String, String>> {
+    let mut extended = binding.clone();
+
+    for (term, value) in [
+        (&pattern.entity, &fact.entity),
+        (&pattern.attribute, &fact.attribute),
+        (&pattern.value, &fact.value),
+        (&pattern.source, &fact.source_id),
+    ] {
+        match term_matches(term, value, &extended) {
+            None => return None,
+            Some(Some((name, val))) => {
+                extended.insert(name, val);
+            }
+            Some(None) => {}
+        }
+    }
+
+    Some(extended)
+}
+
+/// Evaluates `patterns` against `facts`, maintaining a set of variable
+/// bindings: the first pattern seeds one binding per matching fact, and each
+/// subsequent pattern joins against already-bound variables (nested-loop
+/// over `facts`) while extending bindings for newly seen variables. Returns
+/// one binding map per row where every variable is consistently bound.
+pub fn evaluate(facts: &[Fact], patterns: &[Pattern]) -> Vec<HashMap<String, String>> {
+    let mut bindings = vec![HashMap::new()];
+
+    for pattern in patterns {
+        let mut next = Vec::new();
+        for binding in &bindings {
+            for fact in facts {
+                if let Some(extended) = try_extend(binding, pattern, fact) {
+                    next.push(extended);
+                }
+            }
+        }
+        bindings = next;
+        if bindings.is_empty() {
+            break;
+        }
+    }
+
+    bindings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fact(entity: &str, attribute: &str, value: &str, source_id: &str) -> Fact {
+        Fact {
+            entity: entity.to_string(),
+            attribute: attribute.to_string(),
+            value: value.to_string(),
+            source_id: source_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_joins_two_clauses_on_a_shared_variable() {
+        let facts = vec![
+            fact("Quercus alba", "leaf_shape", "lobed", "src1"),
+            fact("Quercus alba", "native_range", "eastern US", "src1"),
+            fact("Quercus rubra", "leaf_shape", "lobed", "src1"),
+            fact("Quercus rubra", "native_range", "eastern US", "src2"),
+        ];
+
+        // Every entity whose leaf_shape is lobed and whose native_range is
+        // attested specifically by src1, joined on ?name.
+        let patterns = vec![
+            parse_pattern("[?name leaf_shape lobed]").unwrap(),
+            parse_pattern("[?name native_range _ src1]").unwrap(),
+        ];
+
+        let results = evaluate(&facts, &patterns);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].get("name"), Some(&"Quercus alba".to_string()));
+    }
+
+    #[test]
+    fn test_evaluate_returns_no_rows_when_join_fails() {
+        let facts = vec![
+            fact("Quercus alba", "leaf_shape", "lobed", "src1"),
+            fact("Quercus alba", "native_range", "eastern US", "src2"),
+        ];
+
+        let patterns = vec![
+            parse_pattern("[?name leaf_shape lobed]").unwrap(),
+            parse_pattern("[?name native_range _ src1]").unwrap(),
+        ];
+
+        assert!(evaluate(&facts, &patterns).is_empty());
+    }
+}
@@ -0,0 +1,269 @@
+use anyhow::{Context, Result};
+use redb::{Database as RedbDb, ReadableTable, TableDefinition};
+use std::path::Path;
+
+use crate::models::{AttributeDef, DataPoint, OakEntry, Source};
+use crate::repository::Repository;
+
+const SOURCES: TableDefinition<&str, &str> = TableDefinition::new("sources");
+/// scientific_name -> JSON-encoded synonyms list; membership in this table
+/// is what makes an oak entry exist, independent of whether it has any
+/// data points yet.
+const OAK_NAMES: TableDefinition<&str, &str> = TableDefinition::new("oak_names");
+/// `"{scientific_name}\0{field_name}"` -> JSON-encoded `Vec<DataPoint>`,
+/// mirroring the entity/attribute/value shape of the SQLite `data_points`
+/// table under a composite key instead of a relational one.
+const DATA_POINTS: TableDefinition<&str, &str> = TableDefinition::new("data_points");
+/// name -> JSON-encoded `AttributeDef`, mirroring the SQLite `attributes`
+/// registry table so a backend round trip (`oak db convert`) doesn't drop
+/// custom-registered attributes.
+const ATTRIBUTES: TableDefinition<&str, &str> = TableDefinition::new("attributes");
+
+/// An embedded key-value storage backend (backed by redb) implementing the
+/// same `Repository` surface as the SQLite-backed `Database`, for
+/// deployments that want a single-file store without SQL.
+pub struct RedbRepository {
+    db: RedbDb,
+}
+
+impl RedbRepository {
+    /// Opens (creating if necessary) a redb-backed repository at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let db = RedbDb::create(path).context("Failed to open redb database")?;
+
+        // Touch every table once so later reads against a brand-new file
+        // see empty tables rather than a "table does not exist" error.
+        let write_txn = db.begin_write()?;
+        {
+            write_txn.open_table(SOURCES)?;
+            write_txn.open_table(OAK_NAMES)?;
+            write_txn.open_table(DATA_POINTS)?;
+            write_txn.open_table(ATTRIBUTES)?;
+        }
+        write_txn.commit()?;
+
+        Ok(Self { db })
+    }
+
+    fn data_point_key(scientific_name: &str, field_name: &str) -> String {
+        format!("{}\0{}", scientific_name, field_name)
+    }
+
+    /// The key prefix every `scientific_name`'s data point rows share;
+    /// `"\0"` sorts below every attribute name character redb field names
+    /// use, so a range scan from this prefix up to (but not including) the
+    /// next scientific_name covers exactly this entry's fields, regardless
+    /// of which attribute names it happens to have.
+    fn data_point_key_prefix(scientific_name: &str) -> String {
+        format!("{}\0", scientific_name)
+    }
+}
+
+/// All (field_name, data_points) pairs currently stored for `scientific_name`,
+/// via a range scan over every key sharing its `"{scientific_name}\0"` prefix.
+fn load_data_points(
+    points: &impl ReadableTable<&str, &str>,
+    scientific_name: &str,
+) -> Result<Vec<(String, Vec<DataPoint>)>> {
+    let prefix = RedbRepository::data_point_key_prefix(scientific_name);
+    let mut fields = Vec::new();
+    for row in points.range(prefix.as_str()..)? {
+        let (key, value) = row?;
+        let key = key.value();
+        let Some(field_name) = key.strip_prefix(prefix.as_str()) else {
+            break;
+        };
+        fields.push((field_name.to_string(), serde_json::from_str(value.value())?));
+    }
+    Ok(fields)
+}
+
+impl Repository for RedbRepository {
+    fn insert_source(&self, source: &Source) -> Result<()> {
+        self.update_source(source)
+    }
+
+    fn get_source(&self, source_id: &str) -> Result<Option<Source>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(SOURCES)?;
+        match table.get(source_id)? {
+            Some(value) => Ok(Some(serde_json::from_str(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    fn update_source(&self, source: &Source) -> Result<()> {
+        let json = serde_json::to_string(source)?;
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(SOURCES)?;
+            table.insert(source.source_id.as_str(), json.as_str())?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    fn list_sources(&self) -> Result<Vec<Source>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(SOURCES)?;
+        let mut sources = table
+            .iter()?
+            .map(|row| -> Result<Source> {
+                let (_, value) = row?;
+                Ok(serde_json::from_str(value.value())?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        sources.sort_by(|a, b| a.source_id.cmp(&b.source_id));
+        Ok(sources)
+    }
+
+    fn search_sources(&self, query: &str) -> Result<Vec<String>> {
+        let needle = query.to_lowercase();
+        Ok(self
+            .list_sources()?
+            .into_iter()
+            .filter(|s| {
+                s.name.to_lowercase().contains(&needle) || s.source_id.to_lowercase().contains(&needle)
+            })
+            .map(|s| s.source_id)
+            .collect())
+    }
+
+    fn save_oak_entry(&self, entry: &OakEntry) -> Result<()> {
+        let synonyms_json = serde_json::to_string(&entry.synonyms)?;
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut names = write_txn.open_table(OAK_NAMES)?;
+            names.insert(entry.scientific_name.as_str(), synonyms_json.as_str())?;
+
+            let mut points = write_txn.open_table(DATA_POINTS)?;
+
+            // Reconcile every field this entry currently asserts something
+            // for, plus any field still stored from a previous save but
+            // absent now, so removed fields don't linger.
+            let mut field_names: std::collections::BTreeSet<String> =
+                entry.attributes.keys().cloned().collect();
+            for (field_name, _) in load_data_points(&points, &entry.scientific_name)? {
+                field_names.insert(field_name);
+            }
+
+            for field_name in field_names {
+                let key = Self::data_point_key(&entry.scientific_name, &field_name);
+                let value = entry.get_field(&field_name);
+                if value.is_empty() {
+                    points.remove(key.as_str())?;
+                } else {
+                    let json = serde_json::to_string(value)?;
+                    points.insert(key.as_str(), json.as_str())?;
+                }
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    fn get_oak_entry(&self, scientific_name: &str) -> Result<Option<OakEntry>> {
+        let read_txn = self.db.begin_read()?;
+
+        let names = read_txn.open_table(OAK_NAMES)?;
+        let synonyms_json = match names.get(scientific_name)? {
+            Some(value) => value.value().to_string(),
+            None => return Ok(None),
+        };
+
+        let mut entry = OakEntry::new(scientific_name.to_string());
+        entry.synonyms = serde_json::from_str(&synonyms_json)?;
+
+        let points = read_txn.open_table(DATA_POINTS)?;
+        for (field_name, data_points) in load_data_points(&points, scientific_name)? {
+            entry.set_field(&field_name, data_points);
+        }
+
+        Ok(Some(entry))
+    }
+
+    fn delete_oak_entry(&self, scientific_name: &str) -> Result<()> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut names = write_txn.open_table(OAK_NAMES)?;
+            names.remove(scientific_name)?;
+
+            let mut points = write_txn.open_table(DATA_POINTS)?;
+            let field_names: Vec<String> = load_data_points(&points, scientific_name)?
+                .into_iter()
+                .map(|(field_name, _)| field_name)
+                .collect();
+            for field_name in field_names {
+                points.remove(Self::data_point_key(scientific_name, &field_name).as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    fn list_oak_entries(&self) -> Result<Vec<OakEntry>> {
+        let names: Vec<String> = {
+            let read_txn = self.db.begin_read()?;
+            let table = read_txn.open_table(OAK_NAMES)?;
+            table
+                .iter()?
+                .map(|row| row.map(|(key, _)| key.value().to_string()))
+                .collect::<std::result::Result<_, _>>()?
+        };
+
+        let mut entries = names
+            .into_iter()
+            .map(|name| {
+                self.get_oak_entry(&name)?
+                    .context("Oak entry disappeared while listing")
+            })
+            .collect::<Result<Vec<_>>>()?;
+        entries.sort_by(|a, b| a.scientific_name.cmp(&b.scientific_name));
+        Ok(entries)
+    }
+
+    fn search_oak_entries(&self, query: &str) -> Result<Vec<String>> {
+        let needle = query.to_lowercase();
+        Ok(self
+            .list_oak_entries()?
+            .into_iter()
+            .map(|e| e.scientific_name)
+            .filter(|name| name.to_lowercase().contains(&needle))
+            .collect())
+    }
+
+    fn bulk_import(&self, sources: &[Source], oak_entries: &[OakEntry]) -> Result<()> {
+        for source in sources {
+            self.update_source(source)?;
+        }
+        for entry in oak_entries {
+            self.save_oak_entry(entry)?;
+        }
+        Ok(())
+    }
+
+    fn list_attributes(&self) -> Result<Vec<AttributeDef>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(ATTRIBUTES)?;
+        let mut attributes = table
+            .iter()?
+            .map(|row| -> Result<AttributeDef> {
+                let (_, value) = row?;
+                Ok(serde_json::from_str(value.value())?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        attributes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(attributes)
+    }
+
+    fn register_attribute(&self, attribute: &AttributeDef) -> Result<()> {
+        let json = serde_json::to_string(attribute)?;
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(ATTRIBUTES)?;
+            table.insert(attribute.name.as_str(), json.as_str())?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use anyhow::Result;
+
+use crate::db::Database;
+use crate::models::{AttributeDef, OakEntry, Source};
+
+/// The data-access surface every storage backend implements, so
+/// backend-agnostic code (notably `oak db convert`) can move data between
+/// backends without knowing which one is on either side. SQLite-specific
+/// features with no obvious analogue on a plain key-value store — full-text
+/// search, the datalog query engine, provenance history, encrypted backups —
+/// stay as inherent methods on `Database` rather than joining this trait.
+pub trait Repository {
+    fn insert_source(&self, source: &Source) -> Result<()>;
+    fn get_source(&self, source_id: &str) -> Result<Option<Source>>;
+    fn update_source(&self, source: &Source) -> Result<()>;
+    fn list_sources(&self) -> Result<Vec<Source>>;
+    fn search_sources(&self, query: &str) -> Result<Vec<String>>;
+
+    fn save_oak_entry(&self, entry: &OakEntry) -> Result<()>;
+    fn get_oak_entry(&self, scientific_name: &str) -> Result<Option<OakEntry>>;
+    fn delete_oak_entry(&self, scientific_name: &str) -> Result<()>;
+    fn list_oak_entries(&self) -> Result<Vec<OakEntry>>;
+    fn search_oak_entries(&self, query: &str) -> Result<Vec<String>>;
+
+    /// Writes every source and oak entry into this repository as one bulk
+    /// load, atomic where the backend supports it. Used by `oak db convert`
+    /// to populate a freshly created target repository.
+    fn bulk_import(&self, sources: &[Source], oak_entries: &[OakEntry]) -> Result<()>;
+
+    /// Every registered attribute. Backends with no registry to validate
+    /// attribute names against can rely on this default empty implementation.
+    fn list_attributes(&self) -> Result<Vec<AttributeDef>> {
+        Ok(Vec::new())
+    }
+
+    /// Registers an attribute, making it a legal `OakEntry::attributes` key.
+    /// A no-op by default for backends with no registry to register it into.
+    fn register_attribute(&self, _attribute: &AttributeDef) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Repository for Database {
+    fn insert_source(&self, source: &Source) -> Result<()> {
+        Database::insert_source(self, source)
+    }
+
+    fn get_source(&self, source_id: &str) -> Result<Option<Source>> {
+        Database::get_source(self, source_id)
+    }
+
+    fn update_source(&self, source: &Source) -> Result<()> {
+        Database::update_source(self, source)
+    }
+
+    fn list_sources(&self) -> Result<Vec<Source>> {
+        Database::list_sources(self)
+    }
+
+    fn search_sources(&self, query: &str) -> Result<Vec<String>> {
+        Database::search_sources(self, query)
+    }
+
+    fn save_oak_entry(&self, entry: &OakEntry) -> Result<()> {
+        Database::save_oak_entry(self, entry)
+    }
+
+    fn get_oak_entry(&self, scientific_name: &str) -> Result<Option<OakEntry>> {
+        Database::get_oak_entry(self, scientific_name)
+    }
+
+    fn delete_oak_entry(&self, scientific_name: &str) -> Result<()> {
+        Database::delete_oak_entry(self, scientific_name)
+    }
+
+    fn list_oak_entries(&self) -> Result<Vec<OakEntry>> {
+        Database::list_oak_entries(self)
+    }
+
+    fn search_oak_entries(&self, query: &str) -> Result<Vec<String>> {
+        Database::search_oak_entries(self, query)
+    }
+
+    fn bulk_import(&self, sources: &[Source], oak_entries: &[OakEntry]) -> Result<()> {
+        Database::bulk_import(self, sources, oak_entries)
+    }
+
+    fn list_attributes(&self) -> Result<Vec<AttributeDef>> {
+        Database::list_attributes(self)
+    }
+
+    fn register_attribute(&self, attribute: &AttributeDef) -> Result<()> {
+        Database::register_attribute(self, attribute)
+    }
+}
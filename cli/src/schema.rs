@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Context, Result};
 use jsonschema::JSONSchema;
+use serde::Serialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::fs;
@@ -7,6 +8,25 @@ use std::path::Path;
 
 use crate::models::OakEntry;
 
+/// One error unit in a `ValidationReport`, modeled on the JSON Schema "basic"
+/// output format.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationUnit {
+    /// JSON Pointer into the serialized entry that failed, e.g. `/leaf_shape/0/value`
+    pub instance_location: String,
+    /// JSON Pointer into the schema (or the enumerations table) that rejected it
+    pub keyword_location: String,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+/// Structured, machine-readable validation result for an `OakEntry`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub errors: Vec<ValidationUnit>,
+}
+
 /// Schema validator for Oak entries
 pub struct SchemaValidator {
     schema: JSONSchema,
@@ -53,63 +73,86 @@ impl SchemaValidator {
         })
     }
 
-    /// Validate an oak entry
+    /// Validate an oak entry, returning a human-readable error on failure.
+    ///
+    /// Thin wrapper over `validate_verbose` that formats the report the way
+    /// callers have always seen it; use `validate_verbose` for programmatic access.
     pub fn validate(&self, entry: &OakEntry) -> Result<()> {
+        let report = self.validate_verbose(entry)?;
+
+        if report.valid {
+            return Ok(());
+        }
+
+        let messages: Vec<String> = report
+            .errors
+            .iter()
+            .map(|e| format!("  - {} (at {})", e.message, e.instance_location))
+            .collect();
+
+        Err(anyhow!("Validation failed:\n{}", messages.join("\n")))
+    }
+
+    /// Validate an oak entry, returning a structured report modeled on the
+    /// JSON Schema "basic" output format instead of a formatted error string.
+    pub fn validate_verbose(&self, entry: &OakEntry) -> Result<ValidationReport> {
         // Convert to JSON for validation
         let json_value = serde_json::to_value(entry)
             .context("Failed to serialize entry")?;
 
-        // Validate against schema
-        if let Err(errors) = self.schema.validate(&json_value) {
-            let error_messages: Vec<String> = errors
-                .map(|e| format!("  - {}", e))
-                .collect();
+        let mut errors = Vec::new();
 
-            return Err(anyhow!(
-                "Validation failed:\n{}",
-                error_messages.join("\n")
-            ));
+        // Validate against schema
+        if let Err(schema_errors) = self.schema.validate(&json_value) {
+            for e in schema_errors {
+                errors.push(ValidationUnit {
+                    instance_location: e.instance_path.to_string(),
+                    keyword_location: e.schema_path.to_string(),
+                    message: e.to_string(),
+                });
+            }
         }
 
         // Validate enumeration values
-        self.validate_enumerations(entry)?;
+        errors.extend(self.validate_enumerations(entry));
 
-        Ok(())
+        Ok(ValidationReport {
+            valid: errors.is_empty(),
+            errors,
+        })
     }
 
-    /// Validate that enumerated field values are in the allowed list
-    fn validate_enumerations(&self, entry: &OakEntry) -> Result<()> {
+    /// Validate that enumerated field values are in the allowed list, returning
+    /// one `ValidationUnit` per violation with `keyword_location` pointing at
+    /// the relevant `enumerations/<field>` entry.
+    fn validate_enumerations(&self, entry: &OakEntry) -> Vec<ValidationUnit> {
         let mut errors = Vec::new();
 
         // Helper to check field values
         let mut check_field = |field_name: &str, values: &[crate::models::DataPoint]| {
             if let Some(allowed) = self.enumerations.get(field_name) {
-                for dp in values {
+                for (i, dp) in values.iter().enumerate() {
                     if !allowed.contains(&dp.value) {
-                        errors.push(format!(
-                            "Invalid value '{}' for field '{}'. Allowed values: {}",
-                            dp.value,
-                            field_name,
-                            allowed.join(", ")
-                        ));
+                        errors.push(ValidationUnit {
+                            instance_location: format!("/{}/{}/value", field_name, i),
+                            keyword_location: format!("/enumerations/{}", field_name),
+                            message: format!(
+                                "Invalid value '{}' for field '{}'. Allowed values: {}",
+                                dp.value,
+                                field_name,
+                                allowed.join(", ")
+                            ),
+                        });
                     }
                 }
             }
         };
 
-        check_field("leaf_color", &entry.leaf_color);
-        check_field("bud_shape", &entry.bud_shape);
-        check_field("leaf_shape", &entry.leaf_shape);
-        check_field("bark_texture", &entry.bark_texture);
-
-        if !errors.is_empty() {
-            return Err(anyhow!(
-                "Enumeration validation failed:\n  - {}",
-                errors.join("\n  - ")
-            ));
+        for field_name in self.enumerations.keys() {
+            check_field(field_name, entry.get_field(field_name));
         }
 
-        Ok(())
+        errors
     }
 
     /// Add a new enumeration value to a field
@@ -179,15 +222,15 @@ mod tests {
     fn test_validate_valid_entry() {
         let validator = SchemaValidator::from_file("schema/oak_schema.json").unwrap();
 
-        let entry = OakEntry {
-            scientific_name: "Quercus robur".to_string(),
-            leaf_shape: vec![DataPoint {
+        let mut entry = OakEntry::new("Quercus robur".to_string());
+        entry.set_field(
+            "leaf_shape",
+            vec![DataPoint {
                 value: "lobed".to_string(),
                 source_id: "src1".to_string(),
                 page_number: None,
             }],
-            ..OakEntry::new("Quercus robur".to_string())
-        };
+        );
 
         assert!(validator.validate(&entry).is_ok());
     }
@@ -196,16 +239,38 @@ mod tests {
     fn test_validate_invalid_enum() {
         let validator = SchemaValidator::from_file("schema/oak_schema.json").unwrap();
 
-        let entry = OakEntry {
-            scientific_name: "Quercus robur".to_string(),
-            leaf_shape: vec![DataPoint {
+        let mut entry = OakEntry::new("Quercus robur".to_string());
+        entry.set_field(
+            "leaf_shape",
+            vec![DataPoint {
                 value: "square".to_string(), // Invalid value
                 source_id: "src1".to_string(),
                 page_number: None,
             }],
-            ..OakEntry::new("Quercus robur".to_string())
-        };
+        );
 
         assert!(validator.validate(&entry).is_err());
     }
+
+    #[test]
+    fn test_validate_verbose_reports_instance_location() {
+        let validator = SchemaValidator::from_file("schema/oak_schema.json").unwrap();
+
+        let mut entry = OakEntry::new("Quercus robur".to_string());
+        entry.set_field(
+            "leaf_shape",
+            vec![DataPoint {
+                value: "square".to_string(), // Invalid value
+                source_id: "src1".to_string(),
+                page_number: None,
+            }],
+        );
+
+        let report = validator.validate_verbose(&entry).unwrap();
+        assert!(!report.valid);
+        assert!(report
+            .errors
+            .iter()
+            .any(|e| e.instance_location == "/leaf_shape/0/value"));
+    }
 }
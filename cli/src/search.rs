@@ -0,0 +1,224 @@
+//! Typo-tolerant, ranked search over oak entries and sources.
+//!
+//! Unlike the plain substring search in `db::Database`, this module tokenizes
+//! indexed text into words and matches query tokens against them using
+//! Levenshtein edit distance, then ranks hits with a bucket sort over
+//! (fewest typos, most query words matched, tightest word proximity, field
+//! importance).
+
+use crate::models::{DataPoint, OakEntry, Source};
+
+/// Computes the Levenshtein edit distance between two strings.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+
+    if n == 0 {
+        return m;
+    }
+    if m == 0 {
+        return n;
+    }
+
+    let mut prev: Vec<usize> = (0..=m).collect();
+    let mut curr = vec![0usize; m + 1];
+
+    for i in 1..=n {
+        curr[0] = i;
+        for j in 1..=m {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[m]
+}
+
+/// Maximum number of typos tolerated for a query token of the given length.
+fn typo_threshold(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+/// Relative importance of a field when breaking ranking ties.
+fn field_weight(field_name: &str) -> u32 {
+    match field_name {
+        "scientific_name" => 100,
+        "synonyms" | "common_names" => 80,
+        "name" | "author" => 60,
+        _ => 40,
+    }
+}
+
+/// One indexed word, tracked with enough context to score and rank a match.
+struct IndexedWord {
+    word: String,
+    position: usize,
+    field_name: String,
+}
+
+struct IndexedDoc {
+    id: String,
+    words: Vec<IndexedWord>,
+}
+
+/// A single query token matched against the best-fitting indexed word.
+struct WordMatch {
+    typos: usize,
+    position: usize,
+    field_name: String,
+}
+
+struct RankedResult {
+    id: String,
+    total_typos: usize,
+    words_matched: usize,
+    proximity: usize,
+    weight: u32,
+}
+
+fn push_text(words: &mut Vec<IndexedWord>, field_name: &str, text: &str, start_pos: usize) -> usize {
+    let mut pos = start_pos;
+    for tok in tokenize(text) {
+        words.push(IndexedWord {
+            word: tok,
+            position: pos,
+            field_name: field_name.to_string(),
+        });
+        pos += 1;
+    }
+    pos
+}
+
+fn push_datapoints(words: &mut Vec<IndexedWord>, field_name: &str, points: &[DataPoint]) {
+    let mut pos = 0;
+    for dp in points {
+        pos = push_text(words, field_name, &dp.value, pos);
+    }
+}
+
+fn build_doc_oak(entry: &OakEntry) -> IndexedDoc {
+    let mut words = Vec::new();
+
+    push_text(&mut words, "scientific_name", &entry.scientific_name, 0);
+    let mut syn_pos = 0;
+    for syn in &entry.synonyms {
+        syn_pos = push_text(&mut words, "synonyms", syn, syn_pos);
+    }
+
+    for (field_name, points) in &entry.attributes {
+        push_datapoints(&mut words, field_name, points);
+    }
+
+    IndexedDoc {
+        id: entry.scientific_name.clone(),
+        words,
+    }
+}
+
+fn build_doc_source(source: &Source) -> IndexedDoc {
+    let mut words = Vec::new();
+
+    push_text(&mut words, "name", &source.name, 0);
+    if let Some(author) = &source.author {
+        push_text(&mut words, "author", author, 0);
+    }
+
+    IndexedDoc {
+        id: source.source_id.clone(),
+        words,
+    }
+}
+
+/// Ranks documents against a query, best match first.
+///
+/// Ranking criteria, in order: fewest total typos, most query words matched,
+/// tightest word proximity for matched words sharing a field, highest field
+/// importance.
+fn rank_docs(docs: &[IndexedDoc], query: &str) -> Vec<String> {
+    let query_tokens = tokenize(query);
+    if query_tokens.is_empty() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<RankedResult> = Vec::new();
+
+    for doc in docs {
+        let matches_per_token: Vec<Option<WordMatch>> = query_tokens
+            .iter()
+            .map(|qt| {
+                let threshold = typo_threshold(qt.chars().count());
+                doc.words
+                    .iter()
+                    .filter_map(|w| {
+                        let dist = levenshtein(qt, &w.word);
+                        (dist <= threshold).then_some(WordMatch {
+                            typos: dist,
+                            position: w.position,
+                            field_name: w.field_name.clone(),
+                        })
+                    })
+                    .min_by_key(|m| m.typos)
+            })
+            .collect();
+
+        let matched: Vec<&WordMatch> = matches_per_token.iter().filter_map(|m| m.as_ref()).collect();
+        if matched.is_empty() {
+            continue;
+        }
+
+        let total_typos: usize = matched.iter().map(|m| m.typos).sum();
+        let weight = matched.iter().map(|m| field_weight(&m.field_name)).max().unwrap_or(0);
+
+        let mut proximity = 0usize;
+        for pair in matches_per_token.windows(2) {
+            if let [Some(a), Some(b)] = pair {
+                if a.field_name == b.field_name {
+                    proximity += b.position.abs_diff(a.position);
+                }
+            }
+        }
+
+        scored.push(RankedResult {
+            id: doc.id.clone(),
+            total_typos,
+            words_matched: matched.len(),
+            proximity,
+            weight,
+        });
+    }
+
+    scored.sort_by(|a, b| {
+        a.total_typos
+            .cmp(&b.total_typos)
+            .then(b.words_matched.cmp(&a.words_matched))
+            .then(a.proximity.cmp(&b.proximity))
+            .then(b.weight.cmp(&a.weight))
+    });
+
+    scored.into_iter().map(|r| r.id).collect()
+}
+
+/// Fuzzy, ranked search over oak entries. Returns scientific names, best match first.
+pub fn fuzzy_search_oaks(entries: &[OakEntry], query: &str) -> Vec<String> {
+    let docs: Vec<IndexedDoc> = entries.iter().map(build_doc_oak).collect();
+    rank_docs(&docs, query)
+}
+
+/// Fuzzy, ranked search over sources. Returns source IDs, best match first.
+pub fn fuzzy_search_sources(sources: &[Source], query: &str) -> Vec<String> {
+    let docs: Vec<IndexedDoc> = sources.iter().map(build_doc_source).collect();
+    rank_docs(&docs, query)
+}